@@ -0,0 +1,124 @@
+// Pathfinding benchmarks: weighted A* across the generated islands, and A*
+// against jump point search on open uniform-cost ground.
+//
+// Run with `cargo bench --bench pathfinding`.
+
+// The game is a binary crate, so pull the modules the grid depends on in directly
+#![allow(dead_code)]
+#[path = "../src/resource_types.rs"]
+mod resource_types;
+#[path = "../src/tile_map.rs"]
+mod tile_map;
+#[path = "../src/terrain.rs"]
+mod terrain;
+#[path = "../src/pathfinding.rs"]
+mod pathfinding;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use pathfinding::{Grid, Position};
+use terrain::TerrainGenerator;
+use tile_map::{TileKind, TileMap};
+
+// Seeds and sizes of the islands in islands.rs
+const ISLANDS: [(&str, u64, i32, i32); 4] = [
+    ("starter_grove", 0x5EED_0001, 96, 30),
+    ("copper_cliffs", 0xC0FF_EE42, 120, 36),
+    ("whispering_woods", 0x0B5E_55ED, 140, 40),
+    ("ember_isle", 0xE3BE_7155, 160, 44),
+];
+
+// Landing spot in the middle to the walkable tile nearest a far corner
+fn endpoints(map: &TileMap) -> (Position, Position) {
+    let (sx, sy) = map.nearest_walkable(map.width() / 2, map.height() / 2).unwrap();
+    let (gx, gy) = map.nearest_walkable(map.width() / 8, map.height() / 5).unwrap();
+    (Position::new(sx, sy), Position::new(gx, gy))
+}
+
+// Open grass with a regular scatter of rocks, like a field of resource nodes
+fn open_field(width: i32, height: i32) -> TileMap {
+    let tiles = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| if x % 9 == 4 && y % 5 != 0 { TileKind::Rock } else { TileKind::Grass })
+        .collect();
+    TileMap::new(width, height, tiles)
+}
+
+fn bench_islands(c: &mut Criterion) {
+    let mut group = c.benchmark_group("island_a_star");
+    for (name, seed, width, height) in ISLANDS {
+        let map = TerrainGenerator::new(seed, width, height).generate();
+        let (start, goal) = endpoints(&map);
+        let mut grid = Grid::new();
+        grid.set_tile_map(&map);
+        grid.set_diagonal(true);
+
+        group.bench_function(name, |b| {
+            b.iter(|| grid.find_path(black_box(start.clone()), black_box(goal.clone())))
+        });
+    }
+    group.finish();
+}
+
+fn bench_straight_vs_diagonal(c: &mut Criterion) {
+    let (_, seed, width, height) = ISLANDS[3];
+    let map = TerrainGenerator::new(seed, width, height).generate();
+    let (start, goal) = endpoints(&map);
+
+    let mut group = c.benchmark_group("movement");
+    for diagonal in [false, true] {
+        let mut grid = Grid::new();
+        grid.set_tile_map(&map);
+        grid.set_diagonal(diagonal);
+        let label = if diagonal { "8_way" } else { "4_way" };
+        group.bench_function(label, |b| {
+            b.iter(|| grid.find_path(black_box(start.clone()), black_box(goal.clone())))
+        });
+    }
+    group.finish();
+}
+
+fn bench_jump_points(c: &mut Criterion) {
+    let mut group = c.benchmark_group("uniform_field");
+    for size in [80, 160, 320] {
+        let map = open_field(size, size / 2);
+        let start = Position::new(1, 1);
+        let goal = Position::new(size - 2, size / 2 - 2);
+
+        for jump_points in [false, true] {
+            let mut grid = Grid::new();
+            grid.set_tile_map(&map);
+            grid.set_diagonal(true);
+            grid.set_jump_points(jump_points);
+            let label = if jump_points { "jps" } else { "a_star" };
+            group.bench_with_input(BenchmarkId::new(label, size), &size, |b, _| {
+                b.iter(|| grid.find_path(black_box(start.clone()), black_box(goal.clone())))
+            });
+        }
+    }
+    group.finish();
+}
+
+// Rebuilding the obstacle layer, which the game now only does when resources change
+fn bench_obstacle_rebuild(c: &mut Criterion) {
+    let (_, seed, width, height) = ISLANDS[3];
+    let map = TerrainGenerator::new(seed, width, height).generate();
+    let mut grid = Grid::new();
+    grid.set_tile_map(&map);
+
+    c.bench_function("obstacle_rebuild_20_nodes", |b| {
+        b.iter(|| {
+            grid.clear_obstacles();
+            for node in 0..20 {
+                let (x, y) = ((node * 7) % width, (node * 3) % height);
+                for dy in 0..4 {
+                    for dx in 0..4 {
+                        grid.add_obstacle(Position::new(x + dx, y + dy));
+                    }
+                }
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_islands, bench_straight_vs_diagonal, bench_jump_points, bench_obstacle_rebuild);
+criterion_main!(benches);
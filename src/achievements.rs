@@ -0,0 +1,246 @@
+// Achievements: milestones checked against the lifetime stats and what the
+// player has crafted and visited, plus a few that depend on single words or
+// the current session. Unlocked achievements are stored in the save by id, so
+// names and descriptions can change freely but ids must not.
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crate::save_system::GameStats;
+
+pub enum Goal {
+    Craft(&'static str),
+    WordsCompleted(u32),  // In total, over every session
+    PerfectSession(u32),  // Words finished since starting the game, without a single mistake
+    FastWord { wpm: f32, min_length: u32 },  // Short words are too easy to rush
+    VisitIsland(&'static str),
+    VisitAllIslands,
+}
+
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub goal: Goal,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 8] = [
+    Achievement {
+        id: "first_workbench",
+        name: "Handy",
+        description: "Build your first workbench",
+        goal: Goal::Craft("Workbench"),
+    },
+    Achievement {
+        id: "words_1000",
+        name: "Wordsmith",
+        description: "Finish 1000 words",
+        goal: Goal::WordsCompleted(1000),
+    },
+    Achievement {
+        id: "perfect_session",
+        name: "Flawless",
+        description: "Finish 25 words in one session without a mistake",
+        goal: Goal::PerfectSession(25),
+    },
+    Achievement {
+        id: "fast_word",
+        name: "Quick Fingers",
+        description: "Type a word of 5 or more letters at 80 WPM",
+        goal: Goal::FastWord { wpm: 80.0, min_length: 5 },
+    },
+    Achievement {
+        id: "visit_copper_cliffs",
+        name: "Cliffhanger",
+        description: "Sail to Copper Cliffs",
+        goal: Goal::VisitIsland("Copper Cliffs"),
+    },
+    Achievement {
+        id: "visit_whispering_woods",
+        name: "Into the Woods",
+        description: "Sail to Whispering Woods",
+        goal: Goal::VisitIsland("Whispering Woods"),
+    },
+    Achievement {
+        id: "visit_ember_isle",
+        name: "Firewalker",
+        description: "Sail to Ember Isle",
+        goal: Goal::VisitIsland("Ember Isle"),
+    },
+    Achievement {
+        id: "visit_all_islands",
+        name: "Seasoned Sailor",
+        description: "Set foot on every island",
+        goal: Goal::VisitAllIslands,
+    },
+];
+
+// Things that happen during play that the stats don't keep
+pub enum GameEvent {
+    WordCompleted { length: u32, wpm: f32 },
+    Mistake,
+}
+
+// Where the player has got to, for the goals that follow from saved progress
+pub struct Progress<'a> {
+    pub stats: &'a GameStats,
+    pub completed_items: &'a [String],
+    pub visited_islands: &'a [String],
+    pub island_count: usize,
+}
+
+pub struct AchievementManager {
+    unlocked: Vec<String>,  // Ids, in the order they were earned
+    session_words: u32,
+    session_mistakes: u32,
+}
+
+impl AchievementManager {
+    // Ids that aren't defined any more are kept, so a newer save loses nothing
+    pub fn new(unlocked: &[String]) -> Self {
+        Self {
+            unlocked: unlocked.to_vec(),
+            session_words: 0,
+            session_mistakes: 0,
+        }
+    }
+
+    pub fn unlocked_ids(&self) -> &[String] {
+        &self.unlocked
+    }
+
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.iter().any(|unlocked| unlocked == id)
+    }
+
+    // Achievements newly earned by saved progress
+    pub fn check(&mut self, progress: &Progress) -> Vec<&'static Achievement> {
+        self.unlock_where(|goal| match goal {
+            Goal::Craft(item) => progress.completed_items.iter().any(|done| done == item),
+            Goal::WordsCompleted(words) => progress.stats.words_completed >= *words,
+            Goal::VisitIsland(island) => progress.visited_islands.iter().any(|visited| visited == island),
+            Goal::VisitAllIslands => progress.visited_islands.len() >= progress.island_count,
+            Goal::PerfectSession(_) | Goal::FastWord { .. } => false,
+        })
+    }
+
+    // Achievements newly earned by something that just happened
+    pub fn record(&mut self, event: GameEvent) -> Vec<&'static Achievement> {
+        match event {
+            GameEvent::Mistake => {
+                self.session_mistakes += 1;
+                Vec::new()
+            }
+            GameEvent::WordCompleted { length, wpm } => {
+                self.session_words += 1;
+                let (words, mistakes) = (self.session_words, self.session_mistakes);
+                self.unlock_where(|goal| match goal {
+                    Goal::PerfectSession(needed) => mistakes == 0 && words >= *needed,
+                    Goal::FastWord { wpm: needed, min_length } => length >= *min_length && wpm >= *needed,
+                    _ => false,
+                })
+            }
+        }
+    }
+
+    fn unlock_where(&mut self, reached: impl Fn(&Goal) -> bool) -> Vec<&'static Achievement> {
+        let mut newly = Vec::new();
+        for achievement in &ACHIEVEMENTS {
+            if !self.is_unlocked(achievement.id) && reached(&achievement.goal) {
+                self.unlocked.push(achievement.id.to_string());
+                newly.push(achievement);
+            }
+        }
+        newly
+    }
+
+    // Every achievement, earned or not, in a box over the game
+    pub fn render(&self, f: &mut Frame, size: Rect) {
+        let earned = ACHIEVEMENTS.iter().filter(|a| self.is_unlocked(a.id)).count();
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("{} of {} unlocked", earned, ACHIEVEMENTS.len()),
+                Style::default().fg(Color::Gray),
+            )),
+            Line::from(""),
+        ];
+        for achievement in &ACHIEVEMENTS {
+            let (mark, style) = if self.is_unlocked(achievement.id) {
+                ("[x] ", Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD))
+            } else {
+                ("[ ] ", Style::default().fg(Color::DarkGray))
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}{}", mark, achievement.name), style),
+                Span::styled(format!(" - {}", achievement.description), Style::default().fg(Color::Gray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("F3, Enter or Esc to close", Style::default().fg(Color::Gray))));
+
+        let width = 72.min(size.width);
+        let height = (lines.len() as u16 + 2).min(size.height);
+        let area = Rect::new(
+            size.x + (size.width - width) / 2,
+            size.y + (size.height - height) / 2,
+            width,
+            height,
+        );
+        let list = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("Achievements"))
+            .alignment(Alignment::Left);
+        f.render_widget(Clear, area);
+        f.render_widget(list, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(achievements: Vec<&Achievement>) -> Vec<&str> {
+        achievements.iter().map(|a| a.id).collect()
+    }
+
+    #[test]
+    fn progress_unlocks_once() {
+        let mut manager = AchievementManager::new(&[]);
+        let stats = GameStats { words_completed: 1000, ..GameStats::default() };
+        let islands = ["Starter Grove".to_string(), "Copper Cliffs".to_string()];
+        let crafted = ["Workbench".to_string()];
+        let progress = Progress {
+            stats: &stats,
+            completed_items: &crafted,
+            visited_islands: &islands,
+            island_count: 4,
+        };
+
+        assert_eq!(ids(manager.check(&progress)), vec!["first_workbench", "words_1000", "visit_copper_cliffs"]);
+        assert!(manager.check(&progress).is_empty());
+    }
+
+    #[test]
+    fn one_mistake_spoils_the_session() {
+        let word = || GameEvent::WordCompleted { length: 4, wpm: 40.0 };
+        let mut clean = AchievementManager::new(&[]);
+        let mut sloppy = AchievementManager::new(&[]);
+        sloppy.record(GameEvent::Mistake);
+        for _ in 0..24 {
+            assert!(clean.record(word()).is_empty());
+            sloppy.record(word());
+        }
+        assert_eq!(ids(clean.record(word())), vec!["perfect_session"]);
+        assert!(sloppy.record(word()).is_empty());
+    }
+
+    #[test]
+    fn fast_words_must_be_long_enough() {
+        let mut manager = AchievementManager::new(&["perfect_session".to_string()]);
+        assert!(manager.record(GameEvent::WordCompleted { length: 3, wpm: 150.0 }).is_empty());
+        assert_eq!(ids(manager.record(GameEvent::WordCompleted { length: 5, wpm: 80.0 })), vec!["fast_word"]);
+    }
+}
@@ -0,0 +1,158 @@
+// Where KeyCrafter keeps its files. Normally the platform's per-user
+// directories (XDG on Linux, AppData on Windows, Application Support on macOS),
+// so progress no longer depends on the directory the game was started from.
+// Portable mode keeps everything in the current directory like older versions did.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use directories::ProjectDirs;
+
+pub const SAVE_FILE_NAME: &str = "keycrafter_save.json";
+const LOG_FILE_NAME: &str = "keycrafter.log";
+const KEY_FILE_NAME: &str = "keycrafter.key";
+
+#[derive(Clone, Debug)]
+pub struct AppDirs {
+    pub save_dir: PathBuf,
+    pub config_dir: PathBuf,  // Custom art lives in assets/art under here
+    pub log_dir: PathBuf,
+    pub portable: bool,
+    custom_save_dir: bool,
+}
+
+impl AppDirs {
+    // Platform directories, or the current directory when portable or when the
+    // platform has no home directory to offer. An explicit save dir overrides
+    // only where saves go
+    pub fn resolve(portable: bool, save_dir: Option<PathBuf>) -> Self {
+        let project = if portable { None } else { ProjectDirs::from("", "", "KeyCrafter") };
+        let mut dirs = match project {
+            Some(project) => Self {
+                save_dir: project.data_local_dir().to_path_buf(),
+                config_dir: project.config_dir().to_path_buf(),
+                // Only Linux has a separate state directory for things like logs
+                log_dir: match project.state_dir() {
+                    Some(state) => state.to_path_buf(),
+                    None => project.data_local_dir().join("logs"),
+                },
+                portable: false,
+                custom_save_dir: false,
+            },
+            None => Self {
+                save_dir: PathBuf::from("."),
+                config_dir: PathBuf::from("."),
+                log_dir: PathBuf::from("."),
+                portable: true,
+                custom_save_dir: false,
+            },
+        };
+
+        if let Some(save_dir) = save_dir {
+            dirs.save_dir = save_dir;
+            dirs.custom_save_dir = true;
+        }
+        dirs
+    }
+
+    pub fn create(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.save_dir)?;
+        fs::create_dir_all(&self.config_dir)?;
+        fs::create_dir_all(&self.log_dir)
+    }
+
+    // This install's save signing key
+    pub fn key_file(&self) -> PathBuf {
+        self.config_dir.join(KEY_FILE_NAME)
+    }
+
+    pub fn art_dir(&self) -> PathBuf {
+        self.config_dir.join("assets").join("art")
+    }
+
+    // Directory an older version would have saved to, if it is worth moving
+    // saves out of. Saves only move into the default platform directory
+    pub fn legacy_save_dir(&self) -> Option<PathBuf> {
+        if self.portable || self.custom_save_dir {
+            return None;
+        }
+        let current = std::env::current_dir().ok()?;
+        if same_dir(&current, &self.save_dir) {
+            return None;
+        }
+        Some(current)
+    }
+
+    // Append a timestamped line to the log. Logging is best-effort and never
+    // gets in the way of the game
+    pub fn log(&self, message: &str) {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.log_dir.join(LOG_FILE_NAME));
+        if let Ok(mut file) = file {
+            let _ = writeln!(file, "[{}] {}", seconds, message);
+        }
+    }
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portable_keeps_everything_here() {
+        let dirs = AppDirs::resolve(true, None);
+        assert!(dirs.portable);
+        assert_eq!(dirs.save_dir, PathBuf::from("."));
+        assert_eq!(dirs.config_dir, PathBuf::from("."));
+        assert_eq!(dirs.log_dir, PathBuf::from("."));
+        assert_eq!(dirs.legacy_save_dir(), None);
+    }
+
+    #[test]
+    fn save_dir_only_moves_saves() {
+        let custom = PathBuf::from("elsewhere/saves");
+
+        // Portable or not, --save-dir wins for saves and nothing else
+        let portable = AppDirs::resolve(true, Some(custom.clone()));
+        assert!(portable.portable);
+        assert_eq!(portable.save_dir, custom);
+        assert_eq!(portable.config_dir, PathBuf::from("."));
+        assert_eq!(portable.log_dir, PathBuf::from("."));
+
+        let default = AppDirs::resolve(false, None);
+        let platform = AppDirs::resolve(false, Some(custom.clone()));
+        assert_eq!(platform.portable, default.portable);
+        assert_eq!(platform.save_dir, custom);
+        assert_eq!(platform.config_dir, default.config_dir);
+        assert_eq!(platform.log_dir, default.log_dir);
+
+        // Saves are never migrated into a directory the player picked
+        assert_eq!(platform.legacy_save_dir(), None);
+    }
+
+    #[test]
+    fn platform_dirs_keep_files_apart() {
+        let dirs = AppDirs::resolve(false, None);
+        // Only a machine without a home directory falls back to portable
+        if dirs.portable {
+            return;
+        }
+        assert_ne!(dirs.save_dir, PathBuf::from("."));
+        assert!(dirs.key_file().starts_with(&dirs.config_dir));
+        assert!(dirs.art_dir().starts_with(&dirs.config_dir));
+    }
+}
@@ -1,46 +1,230 @@
+// Resource art is loaded from small text assets. The defaults are embedded in the
+// binary, and files in assets/art/ under the config directory replace them by
+// name. See resources/art/tree.txt for the format.
+
+use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+use ratatui::style::Color;
+
+const DEFAULT_FRAME_MS: u64 = 500;
+
+// Largest art that still fits on the smallest island with room to walk around it
+const MAX_WIDTH: usize = 24;
+const MAX_HEIGHT: usize = 10;
+
+const DEFAULT_ASSETS: [(&str, &str); 2] = [
+    ("tree.txt", include_str!("../resources/art/tree.txt")),
+    ("copper.txt", include_str!("../resources/art/copper.txt")),
+];
+
+// One animation frame: characters plus an optional colour for each
+struct Frame {
+    chars: Vec<Vec<char>>,
+    colors: Vec<Vec<Option<Color>>>,
+}
+
+// Art shown while at least min_percent of the node's harvests remain
+struct Stage {
+    min_percent: u32,
+    frames: Vec<Frame>,
+}
+
+// Which block the next '|' row belongs to while parsing
+enum Section {
+    None,
+    Frame,
+    Colors,
+    Collision,
+}
 
 pub struct AsciiObject {
-    art: Vec<String>,
+    name: String,
+    stages: Vec<Stage>,  // Sorted by min_percent, highest first
+    blocked: Vec<Vec<bool>>,  // Collision mask, separate from the visible art
     width: usize,
     height: usize,
     path_point: (usize, usize),  // Where to path to within the object
+    frame_ms: u64,
 }
 
 impl AsciiObject {
-    pub fn new(art: Vec<&str>, path_point: (usize, usize)) -> Self {
-        let height = art.len();
-        let width = art.iter().map(|line| line.len()).max().unwrap_or(0);
-        
-        // Pad lines to make all objects the same width
-        let art = art.into_iter()
-            .map(|line| {
-                let mut line = line.to_string();
-                while line.len() < width {
-                    line.push(' ');
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut name = None;
+        let mut path_point = None;
+        let mut frame_ms = DEFAULT_FRAME_MS;
+        let mut stages: Vec<Stage> = Vec::new();
+        let mut collision: Vec<Vec<bool>> = Vec::new();
+        let mut section = Section::None;
+
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim_end();
+            let error = |message: &str| format!("line {}: {}", number + 1, message);
+
+            // Data rows keep their spacing, everything after the '|' is art
+            if let Some(row) = line.strip_prefix('|') {
+                match section {
+                    Section::Frame => {
+                        let frame = stages.last_mut().and_then(|s| s.frames.last_mut()).unwrap();
+                        frame.chars.push(row.chars().collect());
+                    }
+                    Section::Colors => {
+                        let frame = stages.last_mut().and_then(|s| s.frames.last_mut()).unwrap();
+                        let mut colors = Vec::new();
+                        for c in row.chars() {
+                            match parse_color(c) {
+                                Some(color) => colors.push(color),
+                                None => return Err(error(&format!("unknown colour '{}'", c))),
+                            }
+                        }
+                        frame.colors.push(colors);
+                    }
+                    Section::Collision => collision.push(row.chars().map(|c| c == '#').collect()),
+                    Section::None => return Err(error("row outside of a frame, colors or collision block")),
                 }
-                line
-            })
-            .collect();
+                continue;
+            }
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
 
-        Self {
-            art,
-            width,
-            height,
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let number_at = |i: usize| -> Result<u64, String> {
+                words.get(i)
+                    .and_then(|w| w.parse().ok())
+                    .ok_or_else(|| error(&format!("'{}' needs a number", words[0])))
+            };
+            match words[0] {
+                "name" => match words.get(1) {
+                    Some(n) => name = Some(n.to_string()),
+                    None => return Err(error("'name' needs a value")),
+                },
+                "path_point" => path_point = Some((number_at(1)? as usize, number_at(2)? as usize)),
+                "frame_ms" => frame_ms = number_at(1)?.max(1),
+                "stage" => {
+                    stages.push(Stage { min_percent: number_at(1)? as u32, frames: Vec::new() });
+                    section = Section::None;
+                }
+                "frame" => {
+                    // Art without any stage lines is a single stage for the whole life
+                    if stages.is_empty() {
+                        stages.push(Stage { min_percent: 0, frames: Vec::new() });
+                    }
+                    stages.last_mut().unwrap().frames.push(Frame { chars: Vec::new(), colors: Vec::new() });
+                    section = Section::Frame;
+                }
+                "colors" => {
+                    let has_frame = stages.last().is_some_and(|s| !s.frames.is_empty());
+                    if !has_frame {
+                        return Err(error("'colors' must follow a frame"));
+                    }
+                    section = Section::Colors;
+                }
+                "collision" => {
+                    collision.clear();
+                    section = Section::Collision;
+                }
+                other => return Err(error(&format!("unknown keyword '{}'", other))),
+            }
+        }
+
+        let name = name.ok_or("missing 'name'")?;
+        let path_point = path_point.ok_or("missing 'path_point'")?;
+        if let Some(stage) = stages.iter().find(|s| s.frames.is_empty()) {
+            return Err(format!("stage {} has no frames", stage.min_percent));
+        }
+        if stages.is_empty() {
+            return Err("no frames".to_string());
+        }
+        stages.sort_by_key(|stage| Reverse(stage.min_percent));
+
+        let mut object = Self {
+            name,
+            stages,
+            blocked: collision,
+            width: 0,
+            height: 0,
             path_point,
+            frame_ms,
+        };
+        object.finish_shape();
+        if object.width > MAX_WIDTH || object.height > MAX_HEIGHT {
+            return Err(format!(
+                "art and collision are {}x{}, at most {}x{} fits on the islands",
+                object.width, object.height, MAX_WIDTH, MAX_HEIGHT
+            ));
+        }
+        if object.path_point.0 >= object.width || object.path_point.1 >= object.height {
+            return Err("path_point is outside the art".to_string());
+        }
+        Ok(object)
+    }
+
+    // Size the object to fit every frame and pad all rows to that box. Without
+    // a collision block the whole box blocks movement. The path point is always open
+    fn finish_shape(&mut self) {
+        let rows = self.stages.iter().flat_map(|s| &s.frames).map(|f| &f.chars);
+        self.width = rows.clone().flatten().map(|row| row.len()).chain(self.blocked.iter().map(|row| row.len())).max().unwrap_or(0);
+        self.height = rows.map(|r| r.len()).chain(std::iter::once(self.blocked.len())).max().unwrap_or(0);
+
+        let (width, height) = (self.width, self.height);
+        for frame in self.stages.iter_mut().flat_map(|s| s.frames.iter_mut()) {
+            frame.chars.resize(height, Vec::new());
+            frame.colors.resize(height, Vec::new());
+            for row in frame.chars.iter_mut() {
+                row.resize(width, ' ');
+            }
+            for row in frame.colors.iter_mut() {
+                row.resize(width, None);
+            }
+        }
+
+        if self.blocked.is_empty() {
+            self.blocked = vec![vec![true; width]; height];
+        }
+        self.blocked.resize(height, Vec::new());
+        for row in self.blocked.iter_mut() {
+            row.resize(width, false);
+        }
+        if let Some(cell) = self.blocked.get_mut(self.path_point.1).and_then(|row| row.get_mut(self.path_point.0)) {
+            *cell = false;
+        }
+    }
+
+    // Character and colour at an offset inside the object, None where it's blank.
+    // percent_left picks the depletion stage and time_ms the animation frame
+    pub fn char_at(&self, dx: usize, dy: usize, percent_left: u32, time_ms: u64) -> Option<(char, Option<Color>)> {
+        if dx >= self.width || dy >= self.height {
+            return None;
+        }
+        let stage = self.stages.iter()
+            .find(|s| s.min_percent <= percent_left)
+            .unwrap_or_else(|| self.stages.last().unwrap());
+        let frame = &stage.frames[(time_ms / self.frame_ms) as usize % stage.frames.len()];
+
+        let c = frame.chars[dy][dx];
+        if c == ' ' {
+            None
+        } else {
+            Some((c, frame.colors[dy][dx]))
         }
     }
 
-    pub fn render_at(&self, x: usize, y: usize) -> Vec<(usize, usize, char)> {
-        let mut chars = Vec::new();
-        for (dy, line) in self.art.iter().enumerate() {
-            for (dx, c) in line.chars().enumerate() {
-                if c != ' ' {
-                    chars.push((x + dx, y + dy, c));
+    // World tiles the object blocks when its top-left corner is at (x, y)
+    pub fn collision_tiles(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut tiles = Vec::new();
+        for (dy, row) in self.blocked.iter().enumerate() {
+            for (dx, blocked) in row.iter().enumerate() {
+                if *blocked {
+                    tiles.push((x + dx, y + dy));
                 }
             }
         }
-        chars
+        tiles
     }
 
     pub fn get_path_point(&self, x: usize, y: usize) -> (usize, usize) {
@@ -52,62 +236,169 @@ impl AsciiObject {
     }
 }
 
+// Colour letters used in an asset's colour mask. Space and '.' mean the
+// resource's own colour
+fn parse_color(c: char) -> Option<Option<Color>> {
+    let color = match c {
+        ' ' | '.' => return Some(None),
+        'r' => Color::Red,
+        'R' => Color::LightRed,
+        'g' => Color::Green,
+        'G' => Color::LightGreen,
+        'y' => Color::Yellow,
+        'Y' => Color::LightYellow,
+        'b' => Color::Blue,
+        'B' => Color::LightBlue,
+        'm' => Color::Magenta,
+        'M' => Color::LightMagenta,
+        'c' => Color::Cyan,
+        'C' => Color::LightCyan,
+        'w' => Color::White,
+        'a' => Color::Gray,
+        'k' => Color::DarkGray,
+        _ => return None,
+    };
+    Some(Some(color))
+}
+
 pub struct ResourceObjects {
     objects: HashMap<String, AsciiObject>,
+    load_errors: Vec<String>,  // Asset files that couldn't be used, shown once at startup
+    started: Instant,  // Clock for the animations
 }
 
 impl ResourceObjects {
-    pub fn new() -> Self {
-        let mut objects = HashMap::new();
-
-        // Tree
-        objects.insert("tree".to_string(), AsciiObject::new(vec![
-            " /\\ ",
-            "/~~\\",
-            " || ",
-            " || ",
-        ], (2, 3))); // Path to bottom of trunk
-
-        // Copper Ore
-        objects.insert("copper".to_string(), AsciiObject::new(vec![
-            " /\\ ",
-            "(Cu)",
-            "\\__/",
-            " || ",
-        ], (2, 3))); // Path to bottom
-
-        // Iron Ore (for future use)
-        objects.insert("iron".to_string(), AsciiObject::new(vec![
-            " /\\ ",
-            "(Fe)",
-            "\\__/",
-            " || ",
-        ], (2, 3)));
-
-        // Gold Ore (for future use)
-        objects.insert("gold".to_string(), AsciiObject::new(vec![
-            " /\\ ",
-            "(Au)",
-            "\\__/",
-            " || ",
-        ], (2, 3)));
-
-        // Herb (for future use)
-        objects.insert("herb".to_string(), AsciiObject::new(vec![
-            " () ",
-            "\\||/",
-            " \\/ ",
-            " || ",
-        ], (2, 3)));
-
-        Self { objects }
+    // Built-in art, overridden by any files in the custom art directory
+    pub fn new(custom_dir: &Path) -> Self {
+        let mut objects = Self {
+            objects: HashMap::new(),
+            load_errors: Vec::new(),
+            started: Instant::now(),
+        };
+
+        for (file, source) in DEFAULT_ASSETS {
+            objects.load(file, source);
+        }
+        objects.load_dir(custom_dir);
+        objects
+    }
+
+    // Replace the defaults with any art found in the assets directory
+    fn load_dir(&mut self, dir: &Path) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return,  // No custom art is the normal case
+        };
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let file = path.display().to_string();
+            match fs::read_to_string(&path) {
+                Ok(source) => self.load(&file, &source),
+                Err(e) => self.load_errors.push(format!("{}: {}", file, e)),
+            }
+        }
+    }
+
+    fn load(&mut self, file: &str, source: &str) {
+        match AsciiObject::parse(source) {
+            Ok(object) => {
+                self.objects.insert(object.name.clone(), object);
+            }
+            Err(e) => self.load_errors.push(format!("{}: {}", file, e)),
+        }
+    }
+
+    pub fn load_errors(&self) -> &[String] {
+        &self.load_errors
+    }
+
+    // Milliseconds since startup, which drives every animation
+    pub fn animation_time(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
     }
 
     pub fn get(&self, name: &str) -> Option<&AsciiObject> {
         self.objects.get(name)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> AsciiObject {
+        AsciiObject::parse(DEFAULT_ASSETS[0].1).unwrap()
+    }
 
-    pub fn add(&mut self, name: String, art: Vec<&str>, path_point: (usize, usize)) {
-        self.objects.insert(name, AsciiObject::new(art, path_point));
+    fn char_at(object: &AsciiObject, dx: usize, dy: usize) -> Option<(char, Option<Color>)> {
+        object.char_at(dx, dy, 100, 0)
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn colour_masks_can_differ_from_the_frame() {
+        // Missing colours fall back to the resource's own
+        let short = AsciiObject::parse("name rock\npath_point 0 1\nframe\n|ab\n|cd\ncolors\n|r\n").unwrap();
+        assert_eq!(char_at(&short, 0, 0), Some(('a', Some(Color::Red))));
+        assert_eq!(char_at(&short, 1, 0), Some(('b', None)));
+        assert_eq!(char_at(&short, 0, 1), Some(('c', None)));
+
+        // Extra colours past the art are dropped and don't grow the object
+        let long = AsciiObject::parse("name rock\npath_point 0 1\nframe\n|ab\n|cd\ncolors\n|rgy\n|rgy\n|rgy\n").unwrap();
+        assert_eq!(long.dimensions(), (2, 2));
+        assert_eq!(char_at(&long, 1, 0), Some(('b', Some(Color::Green))));
+        assert_eq!(char_at(&long, 2, 0), None);
+    }
+
+    #[test]
+    fn missing_collision_blocks_the_whole_art() {
+        let object = AsciiObject::parse("name rock\npath_point 1 1\nframe\n| ^\n|/_\\\n").unwrap();
+        let mut tiles = object.collision_tiles(10, 20);
+        tiles.sort();
+        // Blank corners too, but never the tile the player walks to
+        assert_eq!(tiles, vec![(10, 20), (10, 21), (11, 20), (12, 20), (12, 21)]);
+
+        let masked = AsciiObject::parse("name rock\npath_point 1 1\ncollision\n|.#.\nframe\n| ^\n|/_\\\n").unwrap();
+        assert_eq!(masked.collision_tiles(10, 20), vec![(11, 20)]);
+    }
+
+    #[test]
+    fn stage_follows_the_harvests_remaining() {
+        let tree = tree();
+        let stage_char = |remaining: u32, dx, dy| {
+            let percent_left = remaining * 100 / 3;
+            tree.char_at(dx, dy, percent_left, 0).map(|(c, _)| c)
+        };
+        // Full canopy, then thinned out at 66%, then a stump
+        assert_eq!(stage_char(3, 1, 0), Some('/'));
+        assert_eq!(stage_char(2, 1, 0), None);
+        assert_eq!(stage_char(2, 1, 1), Some('/'));
+        assert_eq!(stage_char(1, 1, 1), None);
+        assert_eq!(stage_char(1, 1, 2), Some('_'));
+    }
+
+    #[test]
+    fn broken_custom_art_keeps_the_default() {
+        let dir = std::env::temp_dir().join(format!("keycrafter-test-art-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("tree.txt"), "name tree\nframe\n|x\n").unwrap();
+        fs::write(dir.join("copper.txt"), format!("name copper\npath_point 0 0\nframe\n|{}\n", "#".repeat(MAX_WIDTH + 1))).unwrap();
+        fs::write(dir.join("stone.txt"), "name stone\npath_point 0 0\nframe\n|o\n").unwrap();
+
+        let objects = ResourceObjects::new(&dir);
+        let errors = objects.load_errors();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors.iter().any(|e| e.contains("tree.txt") && e.contains("path_point")));
+        assert!(errors.iter().any(|e| e.contains("copper.txt") && e.contains("at most")));
+
+        assert_eq!(objects.get("tree").unwrap().dimensions(), tree().dimensions());
+        assert_eq!(objects.get("copper").unwrap().dimensions(), (4, 4));
+        assert_eq!(objects.get("stone").unwrap().dimensions(), (1, 1));
+    }
+}
@@ -0,0 +1,144 @@
+// Maps world coordinates to the visible part of the game area. The camera
+// only scrolls once the player leaves a dead zone in the middle of the view,
+// so the map doesn't jitter on every step.
+
+pub struct Camera {
+    pub x: i32,  // World position of the top-left visible tile
+    pub y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl Camera {
+    pub fn new() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+        }
+    }
+
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.width = width as i32;
+        self.height = height as i32;
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    // Scroll so the target stays inside the dead zone, then clamp to the world
+    pub fn follow(&mut self, target_x: i32, target_y: i32, world_width: i32, world_height: i32) {
+        let margin_x = self.width / 4;
+        let margin_y = self.height / 4;
+
+        if target_x < self.x + margin_x {
+            self.x = target_x - margin_x;
+        } else if target_x >= self.x + self.width - margin_x {
+            self.x = target_x - self.width + margin_x + 1;
+        }
+        if target_y < self.y + margin_y {
+            self.y = target_y - margin_y;
+        } else if target_y >= self.y + self.height - margin_y {
+            self.y = target_y - self.height + margin_y + 1;
+        }
+
+        self.x = Self::clamp_axis(self.x, self.width, world_width);
+        self.y = Self::clamp_axis(self.y, self.height, world_height);
+    }
+
+    // Jump straight to the target, e.g. after arriving on a new island
+    pub fn center_on(&mut self, target_x: i32, target_y: i32, world_width: i32, world_height: i32) {
+        self.x = Self::clamp_axis(target_x - self.width / 2, self.width, world_width);
+        self.y = Self::clamp_axis(target_y - self.height / 2, self.height, world_height);
+    }
+
+    // Worlds smaller than the view are centred, larger ones can't scroll past their edges
+    fn clamp_axis(pos: i32, view: i32, world: i32) -> i32 {
+        if world <= view {
+            -(view - world) / 2
+        } else {
+            pos.clamp(0, world - view)
+        }
+    }
+
+    pub fn to_world(&self, screen_x: u16, screen_y: u16) -> (i32, i32) {
+        (self.x + screen_x as i32, self.y + screen_y as i32)
+    }
+
+    // Screen cell for a world position, if it's currently visible
+    pub fn to_screen(&self, world_x: i32, world_y: i32) -> Option<(u16, u16)> {
+        let sx = world_x - self.x;
+        let sy = world_y - self.y;
+        if sx >= 0 && sy >= 0 && sx < self.width && sy < self.height {
+            Some((sx as u16, sy as u16))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera(width: u16, height: u16) -> Camera {
+        let mut camera = Camera::new();
+        camera.resize(width, height);
+        camera
+    }
+
+    #[test]
+    fn stops_at_the_map_edges() {
+        let mut camera = camera(20, 10);
+        camera.center_on(0, 0, 100, 50);
+        assert_eq!((camera.x, camera.y), (0, 0));
+        camera.center_on(99, 49, 100, 50);
+        assert_eq!((camera.x, camera.y), (80, 40));
+
+        camera.follow(0, 0, 100, 50);
+        assert_eq!((camera.x, camera.y), (0, 0));
+        camera.follow(99, 49, 100, 50);
+        assert_eq!((camera.x, camera.y), (80, 40));
+    }
+
+    #[test]
+    fn only_scrolls_outside_the_dead_zone() {
+        let mut camera = camera(20, 10);
+        camera.center_on(50, 25, 100, 50);
+        assert_eq!((camera.x, camera.y), (40, 20));
+
+        camera.follow(54, 27, 100, 50);
+        assert_eq!((camera.x, camera.y), (40, 20));
+        camera.follow(55, 28, 100, 50);
+        assert_eq!((camera.x, camera.y), (41, 21));
+        camera.follow(44, 21, 100, 50);
+        assert_eq!((camera.x, camera.y), (39, 19));
+    }
+
+    #[test]
+    fn centres_maps_smaller_than_the_view() {
+        let mut camera = camera(20, 10);
+        for (x, y) in [(0, 0), (9, 3), (5, 2)] {
+            camera.follow(x, y, 10, 4);
+            assert_eq!((camera.x, camera.y), (-5, -3));
+        }
+        assert_eq!(camera.to_screen(0, 0), Some((5, 3)));
+        assert_eq!(camera.to_world(5, 3), (0, 0));
+    }
+
+    #[test]
+    fn to_screen_is_none_off_the_view() {
+        let mut camera = camera(20, 10);
+        camera.center_on(50, 25, 100, 50);
+        assert_eq!(camera.to_screen(40, 20), Some((0, 0)));
+        assert_eq!(camera.to_screen(59, 29), Some((19, 9)));
+        assert_eq!(camera.to_screen(60, 29), None);
+        assert_eq!(camera.to_screen(39, 20), None);
+    }
+}
@@ -0,0 +1,234 @@
+// Command line handling. Kept by hand rather than pulling in a parser, the game
+// only takes a handful of options.
+
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: keycrafter [OPTIONS] [COMMAND]
+
+Commands:
+  update                       Download and install the latest version
+  profile list                 List player profiles
+  profile create <NAME>        Add a profile
+  profile rename <NAME> <NEW>  Rename a profile
+  profile delete <NAME>        Delete a profile and all its progress
+  save export <FILE>           Write the profile's save and settings to an archive
+  save import <FILE>           Restore a profile from an archive
+  save show [FILE]             Check and summarise the save, or an archive or save file
+  save reset                   Start the profile over, keeping an archive of the old save
+
+Options:
+  --profile <NAME>    Play as NAME, creating the profile if needed. Also picks
+                      the profile the save commands work on
+  --compress          Gzip the archive written by save export
+  --portable          Keep saves, settings and logs in the current directory
+  --save-dir <DIR>    Read and write saves in DIR
+  -h, --help          Show this help";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Play,
+    Update,
+    Help,
+    Profile(ProfileCommand),
+    Save(SaveCommand),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ProfileCommand {
+    List,
+    Create(String),
+    Rename(String, String),
+    Delete(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SaveCommand {
+    Export(PathBuf),
+    Import(PathBuf),
+    Show(Option<PathBuf>),
+    Reset,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CliOptions {
+    pub command: Command,
+    pub portable: bool,
+    pub save_dir: Option<PathBuf>,
+    pub profile: Option<String>,
+    pub compress: bool,
+}
+
+impl CliOptions {
+    // Parse the arguments after the program name
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self {
+            command: Command::Play,
+            portable: false,
+            save_dir: None,
+            profile: None,
+            compress: false,
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "update" => options.command = Command::Update,
+                "-h" | "--help" => options.command = Command::Help,
+                "--portable" => options.portable = true,
+                "--compress" => options.compress = true,
+                "--save-dir" => match args.next() {
+                    Some(dir) => options.save_dir = Some(PathBuf::from(dir)),
+                    None => return Err("--save-dir needs a directory".to_string()),
+                },
+                "--profile" => match args.next() {
+                    Some(name) => options.profile = Some(name.clone()),
+                    None => return Err("--profile needs a name".to_string()),
+                },
+                "profile" => options.command = Command::Profile(parse_profile_command(&mut args)?),
+                "save" => options.command = Command::Save(parse_save_command(&mut args)?),
+                other => {
+                    if let Some(dir) = other.strip_prefix("--save-dir=") {
+                        options.save_dir = Some(PathBuf::from(dir));
+                    } else if let Some(name) = other.strip_prefix("--profile=") {
+                        options.profile = Some(name.to_string());
+                    } else {
+                        return Err(format!("unknown argument '{}'", other));
+                    }
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+// The words after `profile`
+fn parse_profile_command(args: &mut std::slice::Iter<String>) -> Result<ProfileCommand, String> {
+    let subcommand = args.next().map(|arg| arg.as_str());
+    let mut name = |command: &str| {
+        args.next().cloned().ok_or_else(|| format!("profile {} needs a profile name", command))
+    };
+    match subcommand {
+        None | Some("list") => Ok(ProfileCommand::List),
+        Some("create") => Ok(ProfileCommand::Create(name("create")?)),
+        Some("rename") => {
+            let old = name("rename")?;
+            Ok(ProfileCommand::Rename(old, name("rename")?))
+        }
+        Some("delete") => Ok(ProfileCommand::Delete(name("delete")?)),
+        Some(other) => Err(format!("unknown profile command '{}'", other)),
+    }
+}
+
+// The words after `save`
+fn parse_save_command(args: &mut std::slice::Iter<String>) -> Result<SaveCommand, String> {
+    match args.next().map(|arg| arg.as_str()) {
+        Some("export") => match args.next() {
+            Some(file) => Ok(SaveCommand::Export(PathBuf::from(file))),
+            None => Err("save export needs a file to write".to_string()),
+        },
+        Some("import") => match args.next() {
+            Some(file) => Ok(SaveCommand::Import(PathBuf::from(file))),
+            None => Err("save import needs a file to read".to_string()),
+        },
+        // The file is optional, so only take the next word if it isn't an option
+        Some("show") => match args.clone().next() {
+            Some(file) if !file.starts_with('-') => {
+                args.next();
+                Ok(SaveCommand::Show(Some(PathBuf::from(file))))
+            }
+            _ => Ok(SaveCommand::Show(None)),
+        },
+        Some("reset") => Ok(SaveCommand::Reset),
+        Some(other) => Err(format!("unknown save command '{}'", other)),
+        None => Err("save needs a command: export, import, show or reset".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<CliOptions, String> {
+        let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+        CliOptions::parse(&args)
+    }
+
+    fn command(line: &str) -> Command {
+        parse(line).unwrap().command
+    }
+
+    #[test]
+    fn no_arguments_plays() {
+        let options = parse("").unwrap();
+        assert_eq!(options.command, Command::Play);
+        assert!(!options.portable && !options.compress);
+        assert_eq!(options.save_dir, None);
+        assert_eq!(options.profile, None);
+    }
+
+    #[test]
+    fn options_in_either_form() {
+        let options = parse("--portable --save-dir saves --profile ana --compress").unwrap();
+        assert!(options.portable && options.compress);
+        assert_eq!(options.save_dir, Some(PathBuf::from("saves")));
+        assert_eq!(options.profile.as_deref(), Some("ana"));
+
+        let options = parse("--save-dir=saves --profile=ana").unwrap();
+        assert_eq!(options.save_dir, Some(PathBuf::from("saves")));
+        assert_eq!(options.profile.as_deref(), Some("ana"));
+
+        assert_eq!(command("--help"), Command::Help);
+        assert_eq!(command("-h"), Command::Help);
+        assert_eq!(command("update"), Command::Update);
+    }
+
+    #[test]
+    fn profile_commands() {
+        assert_eq!(command("profile"), Command::Profile(ProfileCommand::List));
+        assert_eq!(command("profile list"), Command::Profile(ProfileCommand::List));
+        assert_eq!(command("profile create ana"), Command::Profile(ProfileCommand::Create("ana".into())));
+        assert_eq!(
+            command("profile rename ana bea"),
+            Command::Profile(ProfileCommand::Rename("ana".into(), "bea".into()))
+        );
+        assert_eq!(command("profile delete ana"), Command::Profile(ProfileCommand::Delete("ana".into())));
+    }
+
+    #[test]
+    fn save_commands() {
+        let options = parse("save export backup.json --compress --profile ana").unwrap();
+        assert_eq!(options.command, Command::Save(SaveCommand::Export("backup.json".into())));
+        assert!(options.compress);
+        assert_eq!(options.profile.as_deref(), Some("ana"));
+
+        assert_eq!(command("save import backup.json"), Command::Save(SaveCommand::Import("backup.json".into())));
+        assert_eq!(command("save reset"), Command::Save(SaveCommand::Reset));
+        assert_eq!(command("save show"), Command::Save(SaveCommand::Show(None)));
+        assert_eq!(command("save show old.json"), Command::Save(SaveCommand::Show(Some("old.json".into()))));
+
+        // An option after show isn't taken as the file
+        let options = parse("save show --profile ana").unwrap();
+        assert_eq!(options.command, Command::Save(SaveCommand::Show(None)));
+        assert_eq!(options.profile.as_deref(), Some("ana"));
+    }
+
+    #[test]
+    fn bad_arguments_are_reported() {
+        let cases = [
+            ("--fly", "unknown argument '--fly'"),
+            ("--save-dir", "--save-dir needs a directory"),
+            ("--profile", "--profile needs a name"),
+            ("profile create", "profile create needs a profile name"),
+            ("profile rename ana", "profile rename needs a profile name"),
+            ("profile wipe", "unknown profile command 'wipe'"),
+            ("save", "save needs a command: export, import, show or reset"),
+            ("save export", "save export needs a file to write"),
+            ("save import", "save import needs a file to read"),
+            ("save undo", "unknown save command 'undo'"),
+        ];
+        for (line, error) in cases {
+            assert_eq!(parse(line).unwrap_err(), error, "for '{}'", line);
+        }
+    }
+}
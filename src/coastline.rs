@@ -1,10 +1,17 @@
 use std::time::Instant;
 use ratatui::style::{Color, Style};
+use crate::tile_map::{TileKind, TileMap};
+use crate::world_clock::{DayPhase, Weather, WorldClock};
 
 pub struct Coastline {
     last_update: Instant,
     wave_frame: usize,
     wave_chars: Vec<&'static str>,
+    last_rain_update: Instant,
+    rain_frame: u32,
+    phase: DayPhase,
+    weather: Weather,
+    lightning: bool,
 }
 
 impl Coastline {
@@ -13,44 +20,101 @@ impl Coastline {
             last_update: Instant::now(),
             wave_frame: 0,
             wave_chars: vec!["~", "≈", "~", "≈"],
+            last_rain_update: Instant::now(),
+            rain_frame: 0,
+            phase: DayPhase::Day,
+            weather: Weather::Clear,
+            lightning: false,
         }
     }
 
-    pub fn update(&mut self) {
-        if self.last_update.elapsed().as_millis() > 500 {  // Update every 500ms
+    pub fn update(&mut self, clock: &WorldClock) {
+        self.phase = clock.phase();
+        self.weather = clock.weather();
+        self.lightning = clock.lightning();
+
+        // Storms whip the waves up twice as fast
+        let wave_ms = if self.weather == Weather::Storm { 250 } else { 500 };
+        if self.last_update.elapsed().as_millis() > wave_ms {
             self.wave_frame = (self.wave_frame + 1) % self.wave_chars.len();
             self.last_update = Instant::now();
         }
+        if self.last_rain_update.elapsed().as_millis() > 100 {  // Rain falls faster than waves roll
+            self.rain_frame = self.rain_frame.wrapping_add(1);
+            self.last_rain_update = Instant::now();
+        }
+    }
+
+    pub fn get_tile(&self, x: i32, y: i32, tile_map: &TileMap) -> (&'static str, Style) {
+        let wave_char = self.wave_chars[self.wave_frame];
+        let kind = tile_map.get(x, y);
+        let (symbol, color) = match kind {
+            // Grass tiles - blank spaces for cleaner look
+            TileKind::Grass => (" ", None),
+            TileKind::Sand => (".", Some(Color::LightYellow)),
+            TileKind::ForestFloor => (",", Some(Color::DarkGray)),
+            TileKind::Rock => ("^", Some(Color::Gray)),
+            TileKind::Road => (":", Some(Color::Yellow)),
+            // Coastal shallows and lakes
+            TileKind::ShallowWater => (wave_char, Some(Color::Cyan)),
+            TileKind::DeepWater => (wave_char, Some(Color::Blue)),
+        };
+
+        if self.lightning {
+            return (symbol, Style::default().fg(Color::White));
+        }
+        if let Some(drop) = self.raindrop(x, y, &kind) {
+            return (drop, Style::default().fg(Color::Blue));
+        }
+        match color {
+            Some(color) => (symbol, Style::default().fg(self.tint(color))),
+            None => (symbol, Style::default()),
+        }
     }
 
-    pub fn get_tile(&self, x: i32, y: i32, width: i32, height: i32) -> (String, Style) {
-        // Define the island shape - oval/circle
-        let center_x = width / 2;
-        let center_y = height / 2;
-        
-        // Make the land area large enough for gameplay (about 70% of the screen)
-        let radius_x = (width * 3 / 5) as f32;  // Increased from width/3 to width*3/5
-        let radius_y = (height * 3 / 5) as f32; // Increased from height/3 to height*3/5
-
-        // Calculate distance from center (normalized)
-        let dx = (x - center_x) as f32 / radius_x;
-        let dy = (y - center_y) as f32 / radius_y;
-        let distance = (dx * dx + dy * dy).sqrt();
-
-        // Adjust thresholds to make the land area larger
-        // Land is now everything within 1.0 of the radius (was 0.8)
-        // Coast is between 1.0 and 1.3 (was 0.8 and 1.2)
-        if distance > 1.0 && distance < 1.3 {
-            // Wave animation for water tiles
-            let wave_char = self.wave_chars[self.wave_frame];
-            (wave_char.to_string(), Style::default().fg(Color::Cyan))
-        } else if distance <= 1.0 {
-            // Land tiles - blank spaces for cleaner look
-            (" ".to_string(), Style::default())
+    // Rain streaks drifting down over land, denser in a storm
+    fn raindrop(&self, x: i32, y: i32, kind: &TileKind) -> Option<&'static str> {
+        let one_in = match self.weather {
+            Weather::Rain => 16,
+            Weather::Storm => 9,
+            _ => return None,
+        };
+        if matches!(kind, TileKind::DeepWater | TileKind::ShallowWater) {
+            return None;
+        }
+        // Cheap hash of the cell, shifted down a row every rain frame
+        let row = y.wrapping_sub(self.rain_frame as i32);
+        let hash = (x.wrapping_mul(73_856_093) ^ row.wrapping_mul(19_349_663)) as u32;
+        if hash.is_multiple_of(one_in) {
+            Some(if self.weather == Weather::Storm { "/" } else { "'" })
         } else {
-            // Deep water
-            let wave_char = self.wave_chars[self.wave_frame];
-            (wave_char.to_string(), Style::default().fg(Color::Blue))
+            None
         }
     }
-} 
\ No newline at end of file
+
+    // Shift a tile colour for the time of day and weather
+    fn tint(&self, color: Color) -> Color {
+        if self.weather == Weather::Fog {
+            return match color {
+                Color::Blue | Color::DarkGray => Color::DarkGray,
+                _ => Color::Gray,
+            };
+        }
+        match self.phase {
+            DayPhase::Night => match color {
+                Color::LightYellow => Color::Yellow,
+                Color::Yellow => Color::DarkGray,
+                Color::Cyan => Color::Blue,
+                Color::Gray => Color::DarkGray,
+                other => other,
+            },
+            // Warm light on the sand and shallows
+            DayPhase::Dawn | DayPhase::Dusk => match color {
+                Color::LightYellow => Color::LightRed,
+                Color::Cyan => Color::LightMagenta,
+                other => other,
+            },
+            DayPhase::Day => color,
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+
+const WPM_WINDOW: usize = 10;  // Number of recent words used for the rolling WPM
+const MAX_COMBO_STEPS: u32 = 10;  // Combo bonus stops growing after this many words
+const COMBO_STEP_BONUS: f32 = 0.1;  // +10% per consecutive clean word
+
+// Result of a completed word, used to scale the harvest
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WordPerformance {
+    pub wpm: f32,
+    pub combo: u32,
+    pub speed_bonus: f32,
+    pub combo_bonus: f32,
+}
+
+impl WordPerformance {
+    pub fn multiplier(&self) -> f32 {
+        1.0 + self.combo_bonus + self.speed_bonus
+    }
+
+    pub fn apply(&self, base_amount: u32) -> u32 {
+        ((base_amount as f32 * self.multiplier()).round() as u32).max(1)
+    }
+}
+
+pub struct ComboTracker {
+    streak: u32,
+    recent_wpm: VecDeque<f32>,
+}
+
+impl ComboTracker {
+    pub fn new() -> Self {
+        Self {
+            streak: 0,
+            recent_wpm: VecDeque::with_capacity(WPM_WINDOW),
+        }
+    }
+
+    // Record a finished word and work out its bonuses
+    pub fn record_word(&mut self, word_length: u32, time_taken: f32, mistakes: u32) -> WordPerformance {
+        // Standard WPM: five characters count as one word
+        let minutes = (time_taken / 60.0).max(0.001);
+        let wpm = (word_length as f32 / 5.0) / minutes;
+
+        // Compare against the rolling average before this word is added
+        let speed_bonus = match self.rolling_wpm() {
+            Some(average) if mistakes == 0 && wpm >= average * 1.3 => 0.5,
+            Some(average) if mistakes == 0 && wpm >= average * 1.1 => 0.25,
+            _ => 0.0,
+        };
+
+        if mistakes == 0 {
+            self.streak += 1;
+        } else {
+            self.streak = 0;
+        }
+
+        if self.recent_wpm.len() == WPM_WINDOW {
+            self.recent_wpm.pop_front();
+        }
+        self.recent_wpm.push_back(wpm);
+
+        WordPerformance {
+            wpm,
+            combo: self.streak,
+            speed_bonus,
+            combo_bonus: self.combo_bonus(),
+        }
+    }
+
+    // A mistake breaks the streak immediately, even before the word is finished
+    pub fn break_combo(&mut self) {
+        self.streak = 0;
+    }
+
+    pub fn streak(&self) -> u32 {
+        self.streak
+    }
+
+    pub fn combo_bonus(&self) -> f32 {
+        self.streak.min(MAX_COMBO_STEPS) as f32 * COMBO_STEP_BONUS
+    }
+
+    pub fn rolling_wpm(&self) -> Option<f32> {
+        if self.recent_wpm.is_empty() {
+            None
+        } else {
+            Some(self.recent_wpm.iter().sum::<f32>() / self.recent_wpm.len() as f32)
+        }
+    }
+
+    // Filled portion of the HUD meter, 0.0 to 1.0
+    pub fn meter_fill(&self) -> f32 {
+        self.streak.min(MAX_COMBO_STEPS) as f32 / MAX_COMBO_STEPS as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Five letters in a second: 60 WPM
+    fn clean_word(tracker: &mut ComboTracker) -> WordPerformance {
+        tracker.record_word(5, 1.0, 0)
+    }
+
+    #[test]
+    fn clean_words_grow_the_streak() {
+        let mut tracker = ComboTracker::new();
+        assert_eq!(tracker.rolling_wpm(), None);
+        for expected in 1..=3 {
+            assert_eq!(clean_word(&mut tracker).combo, expected);
+        }
+        assert!((tracker.combo_bonus() - 0.3).abs() < 1e-6);
+        assert!((tracker.rolling_wpm().unwrap() - 60.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn combo_bonus_is_capped() {
+        let mut tracker = ComboTracker::new();
+        for _ in 0..MAX_COMBO_STEPS + 5 {
+            clean_word(&mut tracker);
+        }
+        assert_eq!(tracker.streak(), MAX_COMBO_STEPS + 5);
+        let cap = MAX_COMBO_STEPS as f32 * COMBO_STEP_BONUS;
+        assert!((tracker.combo_bonus() - cap).abs() < 1e-6);
+        assert_eq!(tracker.meter_fill(), 1.0);
+        assert!((clean_word(&mut tracker).combo_bonus - cap).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mistakes_reset_the_streak() {
+        let mut tracker = ComboTracker::new();
+        clean_word(&mut tracker);
+        clean_word(&mut tracker);
+        tracker.break_combo();
+        assert_eq!(tracker.streak(), 0);
+        assert_eq!(tracker.combo_bonus(), 0.0);
+
+        // A word that had to be started over finishes with no combo or speed bonus
+        clean_word(&mut tracker);
+        let retyped = tracker.record_word(5, 0.5, 1);
+        assert_eq!(retyped.combo, 0);
+        assert_eq!(retyped.speed_bonus, 0.0);
+        assert_eq!(retyped.apply(1), 1);
+    }
+}
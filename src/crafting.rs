@@ -10,6 +10,8 @@ pub struct Recipe {
     pub requirements: HashMap<ResourceType, u32>,
     pub unlocks: Vec<String>,  // Names of things this unlocks (other recipes, upgrades, etc.)
     pub upgrade_count: u32,  // Track how many times this upgrade has been completed
+    pub one_time: bool,  // One-time items disappear from the list once crafted
+    pub level_requirement: u32,  // Player level needed before this recipe shows up
 }
 
 pub struct CraftingManager {
@@ -17,6 +19,7 @@ pub struct CraftingManager {
     unlocked_recipes: Vec<bool>,  // Parallel vec to track what's unlocked
     pub has_workbench: bool,  // Track if workbench has been crafted
     completed_items: Vec<String>,  // Track completed one-time items
+    player_level: u32,  // Mirrors the player's level for recipe gating
 }
 
 impl CraftingManager {
@@ -26,6 +29,7 @@ impl CraftingManager {
             unlocked_recipes: Vec::new(),
             has_workbench: false,
             completed_items: Vec::new(),
+            player_level: 1,
         };
 
         // Add initial recipe - Workbench
@@ -41,6 +45,8 @@ impl CraftingManager {
             requirements: workbench_reqs,
             unlocks: vec!["Advanced Tools".to_string()],
             upgrade_count: 0,
+            one_time: true,
+            level_requirement: 0,
         });
         manager.unlocked_recipes.push(true);  // Workbench is initially available
 
@@ -58,6 +64,8 @@ impl CraftingManager {
             requirements: axe_reqs,
             unlocks: vec![],
             upgrade_count: 0,
+            one_time: false,
+            level_requirement: 0,
         });
         manager.unlocked_recipes.push(false);  // Locked until workbench is built
 
@@ -73,6 +81,75 @@ impl CraftingManager {
             requirements: pickaxe_reqs,
             unlocks: vec![],
             upgrade_count: 0,
+            one_time: false,
+            level_requirement: 0,
+        });
+        manager.unlocked_recipes.push(false);  // Locked until workbench is built
+
+        // Boat - lets the player sail to other islands
+        let mut boat_reqs = HashMap::new();
+        boat_reqs.insert(ResourceType::Wood, 40);
+        boat_reqs.insert(ResourceType::Copper, 20);
+        manager.recipes.push(Recipe {
+            name: "Boat".to_string(),
+            description: "Sail to new islands. Voyages appear below once built.".to_string(),
+            craft_sentence: "I bend long planks over the frame and rivet the copper keel to launch my own boat.".to_string(),
+            current_input: String::new(),
+            requirements: boat_reqs,
+            unlocks: vec!["Voyages".to_string()],
+            upgrade_count: 0,
+            one_time: true,
+            level_requirement: 2,
+        });
+        manager.unlocked_recipes.push(false);  // Locked until workbench is built
+
+        // Upgrade Boots - faster walking in walking mode
+        let mut boots_reqs = HashMap::new();
+        boots_reqs.insert(ResourceType::Wood, 25);
+        boots_reqs.insert(ResourceType::Copper, 10);
+        manager.recipes.push(Recipe {
+            name: "Upgrade Boots".to_string(),
+            description: "+25% walking speed".to_string(),
+            craft_sentence: "I stitch thick leather soles onto my boots so I can stride across the island.".to_string(),
+            current_input: String::new(),
+            requirements: boots_reqs,
+            unlocks: vec![],
+            upgrade_count: 0,
+            one_time: false,
+            level_requirement: 0,
+        });
+        manager.unlocked_recipes.push(false);  // Locked until workbench is built
+
+        // Workers - gather resources over time, even while the game is closed
+        let mut woodcutter_reqs = HashMap::new();
+        woodcutter_reqs.insert(ResourceType::Wood, 30);
+        woodcutter_reqs.insert(ResourceType::Copper, 20);
+        manager.recipes.push(Recipe {
+            name: "Hire Woodcutter".to_string(),
+            description: "+1 Wood a minute, even while you're away".to_string(),
+            craft_sentence: "I hand a sharp axe to a woodcutter who will fell trees while I am busy elsewhere.".to_string(),
+            current_input: String::new(),
+            requirements: woodcutter_reqs,
+            unlocks: vec![],
+            upgrade_count: 0,
+            one_time: false,
+            level_requirement: 3,
+        });
+        manager.unlocked_recipes.push(false);  // Locked until workbench is built
+
+        let mut miner_reqs = HashMap::new();
+        miner_reqs.insert(ResourceType::Wood, 20);
+        miner_reqs.insert(ResourceType::Copper, 30);
+        manager.recipes.push(Recipe {
+            name: "Hire Miner".to_string(),
+            description: "+2 Copper every three minutes, even while you're away".to_string(),
+            craft_sentence: "I light a lantern for a miner who will chip copper from the rock until I return.".to_string(),
+            current_input: String::new(),
+            requirements: miner_reqs,
+            unlocks: vec![],
+            upgrade_count: 0,
+            one_time: false,
+            level_requirement: 3,
         });
         manager.unlocked_recipes.push(false);  // Locked until workbench is built
 
@@ -91,10 +168,34 @@ impl CraftingManager {
         if index == 0 {  // Workbench is special
             !self.has_workbench  // Only show if not yet crafted
         } else {
-            self.has_workbench && self.unlocked_recipes.get(index).copied().unwrap_or(false)
+            let already_built = self.recipes.get(index)
+                .map(|r| r.one_time && self.completed_items.contains(&r.name))
+                .unwrap_or(false);
+            self.has_workbench && !already_built && !self.is_level_locked(index) &&
+                self.unlocked_recipes.get(index).copied().unwrap_or(false)
         }
     }
 
+    pub fn set_player_level(&mut self, level: u32) {
+        self.player_level = level;
+    }
+
+    // Recipe exists and is otherwise available, but the player isn't high enough level yet
+    pub fn is_level_locked(&self, index: usize) -> bool {
+        self.recipes.get(index)
+            .map(|r| r.level_requirement > self.player_level)
+            .unwrap_or(false)
+    }
+
+    pub fn has_item(&self, name: &str) -> bool {
+        self.completed_items.iter().any(|item| item == name)
+    }
+
+    // One-time items once built, repeatable recipes once crafted at least once
+    pub fn has_crafted(&self, name: &str) -> bool {
+        self.has_item(name) || self.recipes.iter().any(|r| r.name == name && r.upgrade_count > 0)
+    }
+
     pub fn get_completed_items(&self) -> &[String] {
         &self.completed_items
     }
@@ -110,6 +211,15 @@ impl CraftingManager {
         if let Some(pickaxe_recipe) = self.recipes.iter_mut().find(|r| r.name == "Upgrade Pickaxe") {
             pickaxe_recipe.upgrade_count = save_data.pickaxe_upgrade_count;
         }
+        if let Some(boots_recipe) = self.recipes.iter_mut().find(|r| r.name == "Upgrade Boots") {
+            boots_recipe.upgrade_count = save_data.boots_upgrade_count;
+        }
+        if let Some(woodcutter_recipe) = self.recipes.iter_mut().find(|r| r.name == "Hire Woodcutter") {
+            woodcutter_recipe.upgrade_count = save_data.woodcutter_count;
+        }
+        if let Some(miner_recipe) = self.recipes.iter_mut().find(|r| r.name == "Hire Miner") {
+            miner_recipe.upgrade_count = save_data.miner_count;
+        }
         
         // Update unlocked recipes based on workbench status
         if self.has_workbench {
@@ -173,6 +283,9 @@ impl CraftingManager {
                     } else {
                         println!("[ERROR] unlocked_recipes and recipes length mismatch: {} vs {}", unlocked_len, recipes_len);
                     }
+                } else if recipe.one_time {
+                    // One-time items are recorded so they can't be crafted again
+                    self.completed_items.push(recipe.name.clone());
                 } else {
                     // For upgrades, increment the upgrade count
                     recipe.upgrade_count += 1;
@@ -214,6 +327,27 @@ impl CraftingManager {
         }
     }
 
+    // Recipes that are part way typed, by name
+    pub fn inputs_in_progress(&self) -> HashMap<String, String> {
+        self.recipes.iter()
+            .filter(|r| !r.current_input.is_empty())
+            .map(|r| (r.name.clone(), r.current_input.clone()))
+            .collect()
+    }
+
+    // Restore typing progress from a save. Input that no longer fits its
+    // sentence, e.g. after the sentence was reworded, is dropped
+    pub fn load_inputs(&mut self, inputs: &HashMap<String, String>) {
+        for recipe in &mut self.recipes {
+            match inputs.get(&recipe.name) {
+                Some(input) if recipe.craft_sentence.starts_with(input.as_str()) => {
+                    recipe.current_input = input.clone();
+                }
+                _ => recipe.current_input.clear(),
+            }
+        }
+    }
+
     pub fn clear_input(&mut self, recipe_index: usize) {
         if let Some(recipe) = self.recipes.get_mut(recipe_index) {
             recipe.current_input.clear();
@@ -242,6 +376,26 @@ impl CraftingManager {
         }
     }
 
+    // Walking speed multiplier from Boots upgrades, +25% per level
+    pub fn get_walk_speed_multiplier(&self) -> f32 {
+        self.recipes.iter()
+            .find(|r| r.name == "Upgrade Boots")
+            .map(|r| 1.0 + r.upgrade_count as f32 * 0.25)
+            .unwrap_or(1.0)
+    }
+
+    // Workers hired to gather each resource
+    pub fn workers(&self) -> Vec<(ResourceType, u32)> {
+        let hired = |name: &str| self.recipes.iter()
+            .find(|r| r.name == name)
+            .map(|r| r.upgrade_count)
+            .unwrap_or(0);
+        vec![
+            (ResourceType::Wood, hired("Hire Woodcutter")),
+            (ResourceType::Copper, hired("Hire Miner")),
+        ]
+    }
+
     // Get the next cost for an upgrade recipe
     pub fn get_next_upgrade_cost(&self, recipe_index: usize) -> HashMap<ResourceType, u32> {
         let mut increased_costs = HashMap::new();
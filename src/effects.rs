@@ -0,0 +1,288 @@
+// Visual effects drawn over the world: floating texts and small ASCII particle
+// bursts. Everything here is a pure function of its age, so positions and
+// colours are worked out when drawing and update() only drops finished effects.
+
+use std::time::{Duration, Instant};
+use rand::Rng;
+use ratatui::style::Color;
+
+// Texts spawned on top of another one are pushed this many rows at most
+const MAX_STACK: i32 = 4;
+const DEFAULT_TEXT_LIFETIME: Duration = Duration::from_millis(900);
+const PARTICLE_GRAVITY: f32 = 12.0;  // Tiles per second squared
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Motion {
+    Rise,
+    Fall,
+    Arc(f32),  // Hop sideways by this many tiles, peaking halfway
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+// How a floating text moves and fades. Colours step through the gradient over
+// the text's life, so the last entry is what it fades out to
+#[derive(Clone, Debug)]
+pub struct TextStyle {
+    pub motion: Motion,
+    pub easing: Easing,
+    pub distance: f32,  // Tiles travelled over the whole lifetime
+    pub lifetime: Duration,
+    pub gradient: Vec<Color>,
+}
+
+impl TextStyle {
+    // Drifts up and fades, the look for most notifications
+    pub fn rising(color: Color) -> Self {
+        Self {
+            motion: Motion::Rise,
+            easing: Easing::EaseOut,
+            distance: 2.0,
+            lifetime: DEFAULT_TEXT_LIFETIME,
+            gradient: fade_gradient(color),
+        }
+    }
+
+    // Sinks and fades, for things that didn't work out
+    pub fn sinking(color: Color) -> Self {
+        Self {
+            motion: Motion::Fall,
+            easing: Easing::EaseInOut,
+            distance: 1.5,
+            lifetime: DEFAULT_TEXT_LIFETIME,
+            gradient: fade_gradient(color),
+        }
+    }
+
+    // Hops sideways in an arc, for bonuses worth a second look
+    pub fn hopping(color: Color, sideways: f32) -> Self {
+        Self {
+            motion: Motion::Arc(sideways),
+            easing: Easing::Linear,
+            distance: 2.0,
+            lifetime: Duration::from_millis(1100),
+            gradient: fade_gradient(color),
+        }
+    }
+
+    pub fn with_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+}
+
+// Hold the colour for most of the life, then dim through grey
+pub fn fade_gradient(color: Color) -> Vec<Color> {
+    vec![color, color, color, Color::Gray, Color::DarkGray]
+}
+
+fn gradient_at(gradient: &[Color], t: f32) -> Color {
+    if gradient.is_empty() {
+        return Color::White;
+    }
+    let step = (t.clamp(0.0, 1.0) * gradient.len() as f32) as usize;
+    gradient[step.min(gradient.len() - 1)]
+}
+
+pub struct FloatingText {
+    text: String,
+    x: f32,
+    y: f32,
+    style: TextStyle,
+    created_at: Instant,
+}
+
+impl FloatingText {
+    fn progress(&self) -> f32 {
+        self.created_at.elapsed().as_secs_f32() / self.style.lifetime.as_secs_f32().max(0.001)
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.created_at.elapsed() < self.style.lifetime
+    }
+
+    pub fn get_position(&self) -> (i32, i32) {
+        let t = self.style.easing.apply(self.progress());
+        let distance = self.style.distance;
+        let (dx, dy) = match self.style.motion {
+            Motion::Rise => (0.0, -distance * t),
+            Motion::Fall => (0.0, distance * t),
+            Motion::Arc(sideways) => (sideways * t, -distance * 4.0 * t * (1.0 - t)),
+        };
+        ((self.x + dx).round() as i32, (self.y + dy).round() as i32)
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn get_color(&self) -> Color {
+        gradient_at(&self.style.gradient, self.progress())
+    }
+
+    // Cells this text covers right now, as (row, first column, last column)
+    fn footprint(&self) -> (i32, i32, i32) {
+        let (x, y) = self.get_position();
+        (y, x, x + self.text.chars().count() as i32 - 1)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParticleKind {
+    WoodChips,
+    Sparks,
+}
+
+impl ParticleKind {
+    fn glyphs(&self) -> &'static [char] {
+        match self {
+            ParticleKind::WoodChips => &['\'', ',', '`', '.'],
+            ParticleKind::Sparks => &['*', '+', '\'', '.'],
+        }
+    }
+
+    fn gradient(&self) -> &'static [Color] {
+        match self {
+            ParticleKind::WoodChips => &[Color::Yellow, Color::Yellow, Color::Green, Color::DarkGray],
+            ParticleKind::Sparks => &[Color::White, Color::LightYellow, Color::Yellow, Color::Red, Color::DarkGray],
+        }
+    }
+
+    // Chips pop out and drop, sparks shoot higher and burn out faster
+    fn launch_speed(&self) -> (f32, f32) {
+        match self {
+            ParticleKind::WoodChips => (4.0, 6.0),
+            ParticleKind::Sparks => (6.0, 9.0),
+        }
+    }
+
+    fn lifetime(&self) -> Duration {
+        match self {
+            ParticleKind::WoodChips => Duration::from_millis(700),
+            ParticleKind::Sparks => Duration::from_millis(500),
+        }
+    }
+}
+
+pub struct Particle {
+    kind: ParticleKind,
+    glyph: char,
+    x: f32,
+    y: f32,
+    velocity: (f32, f32),
+    created_at: Instant,
+}
+
+impl Particle {
+    pub fn is_alive(&self) -> bool {
+        self.created_at.elapsed() < self.kind.lifetime()
+    }
+
+    pub fn get_position(&self) -> (i32, i32) {
+        let t = self.created_at.elapsed().as_secs_f32();
+        let x = self.x + self.velocity.0 * t;
+        let y = self.y + self.velocity.1 * t + 0.5 * PARTICLE_GRAVITY * t * t;
+        (x.round() as i32, y.round() as i32)
+    }
+
+    pub fn get_glyph(&self) -> char {
+        self.glyph
+    }
+
+    pub fn get_color(&self) -> Color {
+        let t = self.created_at.elapsed().as_secs_f32() / self.kind.lifetime().as_secs_f32();
+        gradient_at(self.kind.gradient(), t)
+    }
+}
+
+pub struct EffectsManager {
+    texts: Vec<FloatingText>,
+    particles: Vec<Particle>,
+}
+
+impl EffectsManager {
+    pub fn new() -> Self {
+        Self {
+            texts: Vec::new(),
+            particles: Vec::new(),
+        }
+    }
+
+    pub fn add_text(&mut self, text: String, x: f32, y: f32, color: Color) {
+        self.add_styled_text(text, x, y, TextStyle::rising(color));
+    }
+
+    // Texts that would land on top of one already showing are stacked away from
+    // it, against their direction of travel so they don't run into each other
+    pub fn add_styled_text(&mut self, text: String, x: f32, y: f32, style: TextStyle) {
+        let step = if style.motion == Motion::Fall { -1 } else { 1 };
+        let start = x.round() as i32;
+        let end = start + text.chars().count() as i32 - 1;
+
+        let mut offset: i32 = 0;
+        while offset.abs() < MAX_STACK {
+            let row = y.round() as i32 + offset;
+            let blocked = self.texts.iter()
+                .map(|t| t.footprint())
+                .any(|(r, s, e)| r == row && s <= end && start <= e);
+            if !blocked {
+                break;
+            }
+            offset += step;
+        }
+
+        self.texts.push(FloatingText {
+            text,
+            x,
+            y: y + offset as f32,
+            style,
+            created_at: Instant::now(),
+        });
+    }
+
+    // Throw a handful of particles out from a point
+    pub fn burst(&mut self, kind: ParticleKind, x: f32, y: f32, count: usize) {
+        let mut rng = rand::thread_rng();
+        let (sideways, upward) = kind.launch_speed();
+        for _ in 0..count {
+            let glyphs = kind.glyphs();
+            self.particles.push(Particle {
+                kind,
+                glyph: glyphs[rng.gen_range(0..glyphs.len())],
+                x,
+                y,
+                velocity: (rng.gen_range(-sideways..sideways), -rng.gen_range(upward * 0.5..upward)),
+                created_at: Instant::now(),
+            });
+        }
+    }
+
+    pub fn update(&mut self) {
+        self.texts.retain(|text| text.is_alive());
+        self.particles.retain(|particle| particle.is_alive());
+    }
+
+    pub fn get_texts(&self) -> &[FloatingText] {
+        &self.texts
+    }
+
+    pub fn get_particles(&self) -> &[Particle] {
+        &self.particles
+    }
+}
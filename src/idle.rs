@@ -0,0 +1,187 @@
+// Workers hired at the workbench gather resources on their own: while the game
+// runs, and for a while after it's closed. Production is worked out from real
+// time rather than frames, and time away is capped so leaving the game shut
+// for a month isn't worth more than a good night's sleep.
+
+use std::collections::HashMap;
+use std::time::Instant;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crate::resource_types::ResourceType;
+
+// What one worker gathers in an hour
+pub const WOOD_PER_WOODCUTTER_PER_HOUR: u32 = 60;
+pub const COPPER_PER_MINER_PER_HOUR: u32 = 40;
+// Time away beyond this earns nothing more
+pub const MAX_OFFLINE_SECS: u64 = 8 * 60 * 60;
+// Shorter breaks are paid for without a summary
+const MIN_AWAY_SECS: u64 = 60;
+
+pub fn hourly_rate(resource_type: &ResourceType, workers: u32) -> u32 {
+    let per_worker = match resource_type {
+        ResourceType::Wood => WOOD_PER_WOODCUTTER_PER_HOUR,
+        ResourceType::Copper => COPPER_PER_MINER_PER_HOUR,
+    };
+    workers.saturating_mul(per_worker)
+}
+
+// Whole resources gathered over a number of seconds, rounded down
+pub fn produced(resource_type: &ResourceType, workers: u32, seconds: u64) -> u32 {
+    let amount = hourly_rate(resource_type, workers) as u64 * seconds / 3600;
+    amount.min(u32::MAX as u64) as u32
+}
+
+// Pays out what workers gather while the game is running
+pub struct IdleManager {
+    last_tick: Instant,
+    carry: HashMap<ResourceType, f64>,  // Part-gathered resources not yet paid out
+}
+
+impl IdleManager {
+    pub fn new() -> Self {
+        Self {
+            last_tick: Instant::now(),
+            carry: HashMap::new(),
+        }
+    }
+
+    // Whole resources gathered since the last tick
+    pub fn tick(&mut self, workers: &[(ResourceType, u32)]) -> Vec<(ResourceType, u32)> {
+        let now = Instant::now();
+        let hours = now.duration_since(self.last_tick).as_secs_f64() / 3600.0;
+        self.last_tick = now;
+
+        let mut earned = Vec::new();
+        for (resource_type, count) in workers {
+            let carry = self.carry.entry(resource_type.clone()).or_insert(0.0);
+            *carry += hourly_rate(resource_type, *count) as f64 * hours;
+            let whole = carry.floor();
+            if whole >= 1.0 {
+                *carry -= whole;
+                earned.push((resource_type.clone(), whole as u32));
+            }
+        }
+        earned
+    }
+}
+
+// What the workers gathered while the game was closed
+#[derive(Debug, PartialEq)]
+pub struct AwayReport {
+    pub away_secs: u64,
+    pub earned: Vec<(ResourceType, u32)>,
+}
+
+impl AwayReport {
+    // None when there was nothing to gather. A clock that went backwards
+    // counts as no time away
+    pub fn compute(saved_at: u64, now: u64, workers: &[(ResourceType, u32)]) -> Option<Self> {
+        let away_secs = now.saturating_sub(saved_at);
+        let counted = away_secs.min(MAX_OFFLINE_SECS);
+        let earned: Vec<(ResourceType, u32)> = workers
+            .iter()
+            .map(|(resource_type, count)| (resource_type.clone(), produced(resource_type, *count, counted)))
+            .filter(|(_, amount)| *amount > 0)
+            .collect();
+        if earned.is_empty() {
+            return None;
+        }
+        Some(Self { away_secs, earned })
+    }
+
+    // Not worth a screen of its own after a quick restart
+    pub fn worth_showing(&self) -> bool {
+        self.away_secs >= MIN_AWAY_SECS
+    }
+
+    pub fn capped(&self) -> bool {
+        self.away_secs > MAX_OFFLINE_SECS
+    }
+
+    // Centred box over the game, dismissed by the next key press
+    pub fn render(&self, f: &mut Frame, size: Rect) {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("You were away for {}.", format_duration(self.away_secs))),
+            Line::from("Your workers gathered:"),
+            Line::from(""),
+        ];
+        for (resource_type, amount) in &self.earned {
+            lines.push(Line::from(Span::styled(
+                format!("+{} {}", amount, resource_type.get_display_name()),
+                Style::default().fg(resource_type.get_color()).add_modifier(Modifier::BOLD),
+            )));
+        }
+        if self.capped() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("Workers stop after {} without you", format_duration(MAX_OFFLINE_SECS)),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Press Enter to carry on", Style::default().fg(Color::Gray))));
+
+        let width = 44.min(size.width);
+        let height = (lines.len() as u16 + 2).min(size.height);
+        let area = Rect::new(
+            size.x + (size.width - width) / 2,
+            size.y + (size.height - height) / 2,
+            width,
+            height,
+        );
+        let summary = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("While you were away"))
+            .alignment(Alignment::Center);
+        f.render_widget(Clear, area);
+        f.render_widget(summary, area);
+    }
+}
+
+fn format_duration(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    match (hours, minutes) {
+        (0, 0) => format!("{}s", seconds),
+        (0, _) => format!("{}m", minutes),
+        (_, 0) => format!("{}h", hours),
+        _ => format!("{}h {}m", hours, minutes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKERS: [(ResourceType, u32); 2] = [(ResourceType::Wood, 2), (ResourceType::Copper, 0)];
+
+    #[test]
+    fn earnings_follow_time_away() {
+        let report = AwayReport::compute(1000, 1000 + 30 * 60, &WORKERS).unwrap();
+        assert_eq!(report.earned, vec![(ResourceType::Wood, WOOD_PER_WOODCUTTER_PER_HOUR)]);
+        assert!(report.worth_showing());
+        assert!(!report.capped());
+    }
+
+    #[test]
+    fn time_away_is_capped() {
+        let report = AwayReport::compute(0, MAX_OFFLINE_SECS * 10, &WORKERS).unwrap();
+        let most = produced(&ResourceType::Wood, 2, MAX_OFFLINE_SECS);
+        assert_eq!(report.earned, vec![(ResourceType::Wood, most)]);
+        assert!(report.capped());
+    }
+
+    #[test]
+    fn nothing_without_workers_or_time_away() {
+        let many = [(ResourceType::Wood, 1000)];
+        assert!(!AwayReport::compute(1000, 1000 + MIN_AWAY_SECS - 1, &many).unwrap().worth_showing());
+        assert_eq!(AwayReport::compute(1000, 1000 + 10, &WORKERS), None);
+        assert_eq!(AwayReport::compute(1000, 1000 + 3600, &[(ResourceType::Wood, 0)]), None);
+        assert_eq!(AwayReport::compute(5000, 1000, &WORKERS), None);
+    }
+}
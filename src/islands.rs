@@ -1,5 +1,9 @@
+use std::collections::HashMap;
 use rand::Rng;
 use crate::resource_types::ResourceType;
+use crate::terrain::TerrainGenerator;
+use crate::tile_map::TileMap;
+use crate::world_clock::WorldClock;
 
 pub struct Island {
     pub name: String,
@@ -7,16 +11,25 @@ pub struct Island {
     pub max_nodes: u32,
     pub spawn_chance: f32,  // 0.0 to 1.0
     pub level_requirement: u32,
+    pub voyage_sentence: String,  // Typed to sail to this island
+    pub requires_visited: Option<usize>,  // Island that must be visited first
+    pub terrain_seed: u64,  // Seed for this island's coastline
+    pub map_width: i32,  // World size in tiles, independent of the terminal
+    pub map_height: i32,
 }
 
 pub struct ResourcePool {
     pub resource_type: ResourceType,
     pub weight: u32,  // Higher weight = more likely to spawn
+    pub yield_bonus: u32,  // Extra units on every harvest from this island's nodes
 }
 
 pub struct IslandManager {
     islands: Vec<Island>,
     current_island: usize,
+    visited: Vec<bool>,  // Parallel vec to track which islands have been reached
+    voyage_inputs: Vec<String>,  // Typing progress for each voyage sentence
+    tile_map: TileMap,  // Generated terrain of the current island
 }
 
 impl IslandManager {
@@ -24,6 +37,9 @@ impl IslandManager {
         let mut manager = Self {
             islands: Vec::new(),
             current_island: 0,
+            visited: Vec::new(),
+            voyage_inputs: Vec::new(),
+            tile_map: TerrainGenerator::new(0, 1, 1).generate(),
         };
 
         // Starting island - Basic resources
@@ -33,34 +49,230 @@ impl IslandManager {
                 ResourcePool {
                     resource_type: ResourceType::Wood,
                     weight: 60,
+                    yield_bonus: 0,
                 },
                 ResourcePool {
                     resource_type: ResourceType::Copper,
                     weight: 50,
+                    yield_bonus: 0,
                 },
             ],
             max_nodes: 6,
             spawn_chance: 0.15,  // 15% chance per harvest
             level_requirement: 0,
+            voyage_sentence: "We turn the bow toward home and sail back to the quiet shores of Starter Grove.".to_string(),
+            requires_visited: None,
+            terrain_seed: 0x5EED_0001,
+            map_width: 96,
+            map_height: 30,
         });
 
-        // Future islands can be added here
-        // Example:
-        // manager.islands.push(Island {
-        //     name: "Iron Mountains".to_string(),
-        //     resource_pools: vec![
-        //         ResourcePool { resource_type: ResourceType::Iron, weight: 50 },
-        //         ResourcePool { resource_type: ResourceType::Copper, weight: 30 },
-        //         ResourcePool { resource_type: ResourceType::Wood, weight: 20 },
-        //     ],
-        //     max_nodes: 6,
-        //     spawn_chance: 0.4,
-        //     level_requirement: 5,
-        // });
+        // Copper-rich cliffs - first destination once a boat is built, with richer ore
+        manager.islands.push(Island {
+            name: "Copper Cliffs".to_string(),
+            resource_pools: vec![
+                ResourcePool {
+                    resource_type: ResourceType::Copper,
+                    weight: 80,
+                    yield_bonus: 1,
+                },
+                ResourcePool {
+                    resource_type: ResourceType::Wood,
+                    weight: 20,
+                    yield_bonus: 0,
+                },
+            ],
+            max_nodes: 7,
+            spawn_chance: 0.2,
+            level_requirement: 3,
+            voyage_sentence: "We hoist the sail and steer east toward the glinting walls of Copper Cliffs.".to_string(),
+            requires_visited: Some(0),
+            terrain_seed: 0xC0FF_EE42,
+            map_width: 120,
+            map_height: 36,
+        });
+
+        // Dense forest - lots of trees with heavier logs, very little ore
+        manager.islands.push(Island {
+            name: "Whispering Woods".to_string(),
+            resource_pools: vec![
+                ResourcePool {
+                    resource_type: ResourceType::Wood,
+                    weight: 90,
+                    yield_bonus: 1,
+                },
+                ResourcePool {
+                    resource_type: ResourceType::Copper,
+                    weight: 10,
+                    yield_bonus: 0,
+                },
+            ],
+            max_nodes: 8,
+            spawn_chance: 0.25,
+            level_requirement: 5,
+            voyage_sentence: "Fog rolls over the waves as we row north to the ancient trees of Whispering Woods.".to_string(),
+            requires_visited: Some(1),
+            terrain_seed: 0x0B5E_55ED,
+            map_width: 140,
+            map_height: 40,
+        });
+
+        // Volcanic isle - crowded and balanced, with the best yields for experienced sailors
+        manager.islands.push(Island {
+            name: "Ember Isle".to_string(),
+            resource_pools: vec![
+                ResourcePool {
+                    resource_type: ResourceType::Wood,
+                    weight: 40,
+                    yield_bonus: 1,
+                },
+                ResourcePool {
+                    resource_type: ResourceType::Copper,
+                    weight: 60,
+                    yield_bonus: 2,
+                },
+            ],
+            max_nodes: 10,
+            spawn_chance: 0.3,
+            level_requirement: 8,
+            voyage_sentence: "Smoke rises on the horizon as we brave the warm currents that lead to Ember Isle.".to_string(),
+            requires_visited: Some(2),
+            terrain_seed: 0xE3BE_7155,
+            map_width: 160,
+            map_height: 44,
+        });
+
+        manager.visited = vec![false; manager.islands.len()];
+        manager.visited[0] = true;
+        manager.voyage_inputs = vec![String::new(); manager.islands.len()];
+        manager.tile_map = Self::generate_tile_map(&manager.islands[0]);
 
         manager
     }
 
+    pub fn get_islands(&self) -> &[Island] {
+        &self.islands
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current_island
+    }
+
+    pub fn has_visited(&self, index: usize) -> bool {
+        self.visited.get(index).copied().unwrap_or(false)
+    }
+
+    pub fn visited_names(&self) -> Vec<String> {
+        self.islands.iter()
+            .zip(&self.visited)
+            .filter(|(_, visited)| **visited)
+            .map(|(island, _)| island.name.clone())
+            .collect()
+    }
+
+    pub fn load_visited(&mut self, names: &[String]) {
+        for (idx, island) in self.islands.iter().enumerate() {
+            if names.contains(&island.name) {
+                self.visited[idx] = true;
+            }
+        }
+    }
+
+    // Whether a voyage to this island can be started right now
+    pub fn can_sail_to(&self, index: usize, has_boat: bool, player_level: u32) -> bool {
+        if !has_boat || index == self.current_island {
+            return false;
+        }
+        match self.islands.get(index) {
+            Some(island) => {
+                player_level >= island.level_requirement &&
+                    island.requires_visited.is_none_or(|req| self.has_visited(req))
+            }
+            None => false,
+        }
+    }
+
+    pub fn get_voyage_input(&self, index: usize) -> &str {
+        self.voyage_inputs.get(index).map(|s| s.as_str()).unwrap_or("")
+    }
+
+    // Feed a typed character into a voyage sentence. Returns true if it matched.
+    pub fn handle_voyage_input(&mut self, index: usize, c: char) -> bool {
+        if let (Some(island), Some(input)) = (self.islands.get(index), self.voyage_inputs.get_mut(index)) {
+            let current_pos = input.chars().count();
+            if island.voyage_sentence.chars().nth(current_pos) == Some(c) {
+                input.push(c);
+                true
+            } else {
+                // Wrong letter resets this voyage only
+                input.clear();
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    pub fn is_voyage_complete(&self, index: usize) -> bool {
+        match (self.islands.get(index), self.voyage_inputs.get(index)) {
+            (Some(island), Some(input)) => *input == island.voyage_sentence,
+            _ => false,
+        }
+    }
+
+    // Voyages that are part way typed, by destination name
+    pub fn voyage_inputs_in_progress(&self) -> HashMap<String, String> {
+        self.islands.iter()
+            .zip(&self.voyage_inputs)
+            .filter(|(_, input)| !input.is_empty())
+            .map(|(island, input)| (island.name.clone(), input.clone()))
+            .collect()
+    }
+
+    pub fn load_voyage_inputs(&mut self, inputs: &HashMap<String, String>) {
+        for (island, input) in self.islands.iter().zip(&mut self.voyage_inputs) {
+            match inputs.get(&island.name) {
+                Some(saved) if island.voyage_sentence.starts_with(saved.as_str()) => *input = saved.clone(),
+                _ => input.clear(),
+            }
+        }
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.islands.iter().position(|island| island.name == name)
+    }
+
+    pub fn clear_voyage_inputs(&mut self) {
+        for input in &mut self.voyage_inputs {
+            input.clear();
+        }
+    }
+
+    pub fn travel_to(&mut self, index: usize) -> bool {
+        if index >= self.islands.len() {
+            return false;
+        }
+        self.current_island = index;
+        self.visited[index] = true;
+        self.tile_map = Self::generate_tile_map(&self.islands[index]);
+        self.clear_voyage_inputs();
+        true
+    }
+
+    fn generate_tile_map(island: &Island) -> TileMap {
+        TerrainGenerator::new(island.terrain_seed, island.map_width, island.map_height).generate()
+    }
+
+    pub fn get_tile_map(&self) -> &TileMap {
+        &self.tile_map
+    }
+
+    // Land tile closest to the middle of the map, where the player comes ashore
+    pub fn landing_position(&self) -> (i32, i32) {
+        let center = (self.tile_map.width() / 2, self.tile_map.height() / 2);
+        self.tile_map.nearest_walkable(center.0, center.1).unwrap_or(center)
+    }
+
     pub fn get_current_island(&self) -> &Island {
         &self.islands[self.current_island]
     }
@@ -72,56 +284,64 @@ impl IslandManager {
         roll < island.spawn_chance
     }
 
-    pub fn get_random_resource_type(&self) -> ResourceType {
+    // Extra units per harvest of this resource on the current island
+    pub fn yield_bonus(&self, resource_type: &ResourceType) -> u32 {
+        self.get_current_island()
+            .resource_pools
+            .iter()
+            .find(|pool| &pool.resource_type == resource_type)
+            .map_or(0, |pool| pool.yield_bonus)
+    }
+
+    // Pool weights are scaled by the time of day and weather
+    pub fn get_random_resource_type(&self, clock: &WorldClock) -> ResourceType {
         let island = self.get_current_island();
         let mut rng = rand::thread_rng();
+        let weight = |pool: &ResourcePool| pool.weight as f32 * clock.spawn_multiplier(&pool.resource_type);
         
         // Calculate total weight
-        let total_weight: u32 = island.resource_pools.iter().map(|p| p.weight).sum();
+        let total_weight: f32 = island.resource_pools.iter().map(weight).sum();
         
         // Get random value
-        let mut value = rng.gen_range(0..total_weight);
+        let mut value = rng.gen_range(0.0..total_weight);
         
         // Find corresponding resource
         for pool in &island.resource_pools {
-            if value < pool.weight {
+            if value < weight(pool) {
                 return pool.resource_type.clone();
             }
-            value -= pool.weight;
+            value -= weight(pool);
         }
         
         // Fallback to first resource
         island.resource_pools[0].resource_type.clone()
     }
 
-    pub fn find_spawn_position(&self, existing_positions: &[(i32, i32)], width: i32, height: i32) -> Option<(i32, i32)> {
+    pub fn find_spawn_position(&self, resource_type: &ResourceType, size: (i32, i32), existing_positions: &[(i32, i32)]) -> Option<(i32, i32)> {
         let mut rng = rand::thread_rng();
         let mut attempts = 0;
         const MAX_ATTEMPTS: u32 = 100;
-
-        // Calculate the safe spawn area (within the land)
-        let center_x = width / 2;
-        let center_y = height / 2;
-        let radius_x = (width * 3 / 5) as f32;
-        let radius_y = (height * 3 / 5) as f32;
+        let width = self.tile_map.width();
+        let height = self.tile_map.height();
+        let (object_width, object_height) = size;
+        // Custom art can be too big for the map, with no room left around it
+        if width - object_width <= 1 || height - object_height <= 2 {
+            return None;
+        }
 
         while attempts < MAX_ATTEMPTS {
-            // Generate random position
-            let x = rng.gen_range(4..width-4);  // Leave margin for ASCII art
-            let y = rng.gen_range(4..height-4);
-
-            // Check if position is within the land area
-            let dx = (x - center_x) as f32 / radius_x;
-            let dy = (y - center_y) as f32 / radius_y;
-            let distance = (dx * dx + dy * dy).sqrt();
+            // Generate random position, leaving room above for the word
+            let x = rng.gen_range(1..width - object_width);
+            let y = rng.gen_range(2..height - object_height);
 
-            // Only allow spawning within 90% of the land radius to keep away from coast
-            if distance <= 0.9 {
+            // The whole object must sit on tiles that grow this resource,
+            // with a one tile border of land so it can be walked around
+            if self.tile_map.can_place_resource(resource_type, x, y, object_width, object_height, 1) {
                 // Check if position is far enough from existing nodes
                 let is_valid = existing_positions.iter().all(|(ex, ey)| {
                     let dx = (x - ex).abs();
                     let dy = (y - ey).abs();
-                    dx > 6 || dy > 4  // Minimum distance between nodes
+                    dx > object_width + 2 || dy > object_height  // Minimum distance between nodes
                 });
 
                 if is_valid {
@@ -0,0 +1,201 @@
+// The game world is drawn as a stack of widgets, each writing straight into the
+// frame's buffer: terrain, path preview, objects, word labels, entities, effects
+// and the HUD. Later layers draw over earlier ones. Only the terrain touches
+// every cell; the others walk their own items, so a frame costs roughly
+// cells + items instead of cells × items.
+
+use std::collections::HashSet;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Widget,
+};
+use crate::ascii_objects::AsciiObject;
+use crate::camera::Camera;
+use crate::coastline::Coastline;
+use crate::effects::EffectsManager;
+use crate::pathfinding::Position;
+use crate::resource_types::ResourceType;
+use crate::tile_map::TileMap;
+
+// Set one world tile's cell, if the camera can see it
+fn put(buf: &mut Buffer, area: Rect, camera: &Camera, world_x: i32, world_y: i32, c: char, style: Style) {
+    if let Some((sx, sy)) = camera.to_screen(world_x, world_y) {
+        if sx < area.width && sy < area.height {
+            buf.get_mut(area.x + sx, area.y + sy).set_char(c).set_style(style);
+        }
+    }
+}
+
+pub struct TerrainLayer<'a> {
+    pub camera: &'a Camera,
+    pub tile_map: &'a TileMap,
+    pub coastline: &'a Coastline,
+}
+
+impl Widget for TerrainLayer<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for sy in 0..area.height {
+            for sx in 0..area.width {
+                let (world_x, world_y) = self.camera.to_world(sx, sy);
+                let (symbol, style) = self.coastline.get_tile(world_x, world_y, self.tile_map);
+                buf.get_mut(area.x + sx, area.y + sy).set_symbol(symbol).set_style(style);
+            }
+        }
+    }
+}
+
+// Dotted trail over the terrain where the player is about to walk
+pub struct PathLayer<'a> {
+    pub camera: &'a Camera,
+    pub tiles: &'a HashSet<(i32, i32)>,
+}
+
+impl Widget for PathLayer<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for (x, y) in self.tiles {
+            if let Some((sx, sy)) = self.camera.to_screen(*x, *y) {
+                if sx < area.width && sy < area.height {
+                    // Keep the terrain's background, only swap the symbol
+                    buf.get_mut(area.x + sx, area.y + sy).set_char('·').set_fg(Color::Yellow);
+                }
+            }
+        }
+    }
+}
+
+// One resource node's art, already resolved to a stage and colour
+pub struct Sprite<'a> {
+    pub object: &'a AsciiObject,
+    pub position: Position,  // Top-left corner in the world
+    pub percent_left: u32,
+    pub time_ms: u64,
+    pub color: Color,  // Used where the art has no colour of its own
+    pub greyed: bool,  // Unreachable nodes are drawn all in grey
+}
+
+pub struct ObjectLayer<'a> {
+    pub camera: &'a Camera,
+    pub sprites: &'a [Sprite<'a>],
+}
+
+impl Widget for ObjectLayer<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for sprite in self.sprites {
+            let (width, height) = sprite.object.dimensions();
+            for dy in 0..height {
+                for dx in 0..width {
+                    if let Some((c, color)) = sprite.object.char_at(dx, dy, sprite.percent_left, sprite.time_ms) {
+                        let color = if sprite.greyed { Color::DarkGray } else { color.unwrap_or(sprite.color) };
+                        let x = sprite.position.x + dx as i32;
+                        let y = sprite.position.y + dy as i32;
+                        put(buf, area, self.camera, x, y, c, Style::default().fg(color));
+                    }
+                }
+            }
+        }
+    }
+}
+
+// A run of styled characters anchored at a world position, like a word above a node
+pub struct Label {
+    pub x: i32,
+    pub y: i32,
+    pub cells: Vec<(char, Style)>,
+}
+
+pub struct LabelLayer<'a> {
+    pub camera: &'a Camera,
+    pub labels: &'a [Label],
+}
+
+impl Widget for LabelLayer<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for label in self.labels {
+            for (i, (c, style)) in label.cells.iter().enumerate() {
+                put(buf, area, self.camera, label.x + i as i32, label.y, *c, *style);
+            }
+        }
+    }
+}
+
+pub struct EntityLayer<'a> {
+    pub camera: &'a Camera,
+    pub player: &'a Position,
+}
+
+impl Widget for EntityLayer<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        put(buf, area, self.camera, self.player.x, self.player.y, '@', Style::default().fg(Color::Blue));
+    }
+}
+
+// Particles, then floating texts on top. Texts are positioned in the world but
+// kept fully on screen horizontally
+pub struct EffectsLayer<'a> {
+    pub camera: &'a Camera,
+    pub effects: &'a EffectsManager,
+}
+
+impl Widget for EffectsLayer<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for particle in self.effects.get_particles() {
+            let (x, y) = particle.get_position();
+            put(buf, area, self.camera, x, y, particle.get_glyph(), Style::default().fg(particle.get_color()));
+        }
+
+        for text in self.effects.get_texts() {
+            let (x, y) = text.get_position();
+            if let Some((sx, sy)) = self.camera.to_screen(x, y) {
+                if sy >= area.height {
+                    continue;
+                }
+                let len = text.get_text().chars().count() as u16;
+                let start_x = sx.min(area.width.saturating_sub(len));
+                let style = Style::default().fg(text.get_color()).add_modifier(Modifier::BOLD);
+                for (i, c) in text.get_text().chars().enumerate() {
+                    let cx = start_x + i as u16;
+                    if cx < area.width {
+                        buf.get_mut(area.x + cx, area.y + sy).set_char(c).set_style(style);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Resource counter and crafted items in the top-right corner of the view
+pub struct HudLayer<'a> {
+    pub wood: u32,
+    pub copper: u32,
+    pub completed_items: &'a [String],
+}
+
+impl Widget for HudLayer<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+        let right = area.x + area.width;
+
+        let counter_x = right.saturating_sub(28).max(area.x);
+        let counter = Line::from(vec![
+            Span::styled(format!("Wood: {}", self.wood), Style::default().fg(ResourceType::Wood.get_color())),
+            Span::raw(" | "),
+            Span::styled(format!("Copper: {}", self.copper), Style::default().fg(ResourceType::Copper.get_color())),
+        ]);
+        buf.set_line(counter_x, area.y, &counter, right - counter_x);
+
+        // Crafted items listed from the third line down
+        let items_x = right.saturating_sub(23).max(area.x);
+        for (i, item) in self.completed_items.iter().enumerate() {
+            let y = area.y + 2 + i as u16;
+            if y >= area.y + area.height {
+                break;
+            }
+            buf.set_stringn(items_x, y, item, (right - items_x) as usize, Style::default().fg(Color::Green));
+        }
+    }
+}
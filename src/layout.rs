@@ -0,0 +1,134 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+// Below this the game can't be played at all
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 14;
+
+// Below this the crafting panel is squeezed into a compact summary
+const FULL_MIN_WIDTH: u16 = 90;
+const FULL_MIN_HEIGHT: u16 = 36;
+
+const FULL_CRAFTING_HEIGHT: u16 = 12;
+const COMPACT_CRAFTING_HEIGHT: u16 = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutMode {
+    Full,
+    Compact,
+    TooSmall,
+}
+
+pub struct ScreenLayout {
+    pub mode: LayoutMode,
+    pub game_area: Rect,
+    pub crafting_area: Rect,
+}
+
+pub struct LayoutManager {
+    size: (u16, u16),
+    resized: bool,  // Set on resize until the game has recomputed its positions
+}
+
+impl LayoutManager {
+    pub fn new() -> Self {
+        Self {
+            size: (0, 0),
+            resized: true,
+        }
+    }
+
+    pub fn handle_resize(&mut self, width: u16, height: u16) {
+        if self.size != (width, height) {
+            self.size = (width, height);
+            self.resized = true;
+        }
+    }
+
+    // Returns true once after each resize so dependent positions can be recomputed
+    pub fn take_resized(&mut self) -> bool {
+        std::mem::replace(&mut self.resized, false)
+    }
+
+    // Mode for the size last drawn at
+    pub fn mode(&self) -> LayoutMode {
+        Self::mode_for(self.size.0, self.size.1)
+    }
+
+    pub fn mode_for(width: u16, height: u16) -> LayoutMode {
+        if width < MIN_WIDTH || height < MIN_HEIGHT {
+            LayoutMode::TooSmall
+        } else if width < FULL_MIN_WIDTH || height < FULL_MIN_HEIGHT {
+            LayoutMode::Compact
+        } else {
+            LayoutMode::Full
+        }
+    }
+
+    pub fn compute(&mut self, size: Rect) -> ScreenLayout {
+        // The frame size is authoritative, in case a resize event was missed
+        self.handle_resize(size.width, size.height);
+
+        let mode = Self::mode_for(size.width, size.height);
+        let crafting_height = match mode {
+            LayoutMode::Full => FULL_CRAFTING_HEIGHT,
+            LayoutMode::Compact => COMPACT_CRAFTING_HEIGHT,
+            LayoutMode::TooSmall => 0,
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),  // Game area takes whatever is left
+                Constraint::Length(crafting_height),
+            ])
+            .split(size);
+
+        ScreenLayout {
+            mode,
+            game_area: chunks[0],
+            crafting_area: chunks[1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_thresholds() {
+        assert_eq!(LayoutManager::mode_for(MIN_WIDTH - 1, 50), LayoutMode::TooSmall);
+        assert_eq!(LayoutManager::mode_for(200, MIN_HEIGHT - 1), LayoutMode::TooSmall);
+        assert_eq!(LayoutManager::mode_for(MIN_WIDTH, MIN_HEIGHT), LayoutMode::Compact);
+        assert_eq!(LayoutManager::mode_for(FULL_MIN_WIDTH - 1, FULL_MIN_HEIGHT), LayoutMode::Compact);
+        assert_eq!(LayoutManager::mode_for(FULL_MIN_WIDTH, FULL_MIN_HEIGHT - 1), LayoutMode::Compact);
+        assert_eq!(LayoutManager::mode_for(FULL_MIN_WIDTH, FULL_MIN_HEIGHT), LayoutMode::Full);
+    }
+
+    #[test]
+    fn crafting_panel_shrinks_with_the_mode() {
+        let mut layout = LayoutManager::new();
+        for (width, height, crafting_height) in [(120, 40, FULL_CRAFTING_HEIGHT), (80, 24, COMPACT_CRAFTING_HEIGHT), (30, 10, 0)] {
+            let screen = layout.compute(Rect::new(0, 0, width, height));
+            assert_eq!(screen.crafting_area.height, crafting_height);
+            assert_eq!(screen.game_area.height, height - crafting_height);
+            assert_eq!(screen.game_area.width, width);
+        }
+    }
+
+    #[test]
+    fn resize_is_reported_once() {
+        let mut layout = LayoutManager::new();
+        assert!(layout.take_resized());
+        layout.handle_resize(80, 24);
+        assert!(layout.take_resized());
+        assert!(!layout.take_resized());
+        layout.handle_resize(80, 24);
+        assert!(!layout.take_resized());
+        layout.compute(Rect::new(0, 0, 100, 40));
+        assert!(layout.take_resized());
+        assert_eq!(layout.mode(), LayoutMode::Full);
+        layout.handle_resize(30, 10);
+        assert_eq!(layout.mode(), LayoutMode::TooSmall);
+    }
+}
@@ -1,26 +1,65 @@
 mod pathfinding;
 mod ascii_objects;
-mod floating_text;
+mod effects;
 mod upgrades;
 mod islands;
 mod resource_types;
 mod crafting;
 mod word_lists;
 mod save_system;
+mod save_migrations;
+mod save_integrity;
 mod updater;
 mod coastline;
+mod terrain;
+mod tile_map;
+mod camera;
+mod combo;
+mod progression;
+mod layout;
+mod movement;
+mod layers;
+mod world_clock;
+mod app_dirs;
+mod cli;
+mod profiles;
+mod profile_picker;
+mod save_archive;
+mod world_state;
+mod idle;
+mod achievements;
+mod quests;
 
 use pathfinding::{Grid, Position};
 use ascii_objects::ResourceObjects;
-use floating_text::FloatingTextManager;
+use effects::{EffectsManager, ParticleKind, TextStyle};
 use upgrades::UpgradeManager;
 use islands::IslandManager;
 use resource_types::ResourceType;
 use crafting::CraftingManager;
 use word_lists::{WordList, WordDifficulty};
 use save_system::{SaveData, GameStats, SaveManager};
+use save_migrations::{MigrationError, CURRENT_SAVE_VERSION};
+use save_integrity::{InstallKey, Signature};
 use updater::{Updater, VersionInfo};
 use coastline::Coastline;
+use camera::Camera;
+use tile_map::TileKind;
+use combo::{ComboTracker, WordPerformance};
+use progression::{PlayerLevel, XP_PER_CRAFT, XP_PER_HARVEST, XP_PER_VOYAGE, XP_PER_WORD_CHAR};
+use layout::{LayoutManager, LayoutMode, MIN_HEIGHT, MIN_WIDTH};
+use movement::{MovementMode, QueuedHarvest, Walker};
+use world_clock::WorldClock;
+use app_dirs::AppDirs;
+use cli::{CliOptions, Command, ProfileCommand, SaveCommand};
+use profiles::{Profile, ProfileManager, ProfileSettings, DEFAULT_PROFILE};
+use profile_picker::{PickerAction, ProfilePicker};
+use save_archive::SaveArchive;
+use world_state::{IslandNodes, NodeState, PlayerState, WorldState};
+use idle::{AwayReport, IdleManager};
+use achievements::{Achievement, AchievementManager, GameEvent, Progress, ACHIEVEMENTS};
+use quests::{Quest, QuestContext, QuestManager};
+use layers::{EffectsLayer, EntityLayer, HudLayer, Label, LabelLayer, ObjectLayer, PathLayer, Sprite, TerrainLayer};
 
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind},
@@ -30,15 +69,18 @@ use crossterm::{
 use rand::Rng;
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    layout::{Rect, Alignment},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap, Clear},
     Frame, Terminal,
 };
 use std::{
+    collections::{HashMap, HashSet},
     error::Error,
-    io,
+    io::{self, Write},
+    fs,
+    path::{Path, PathBuf},
     time::{Duration, Instant},
     env,
 };
@@ -56,14 +98,75 @@ struct Resource {
     max_harvests: u32,
     path: Vec<Position>,  // Track path for this resource
     word_start_time: Option<Instant>,  // Track timing for this specific word
+    mistakes: u32,  // Wrong letters typed since this word was assigned
+    reachable: bool,  // False if no tile in harvest range can be walked to
+}
+
+impl Resource {
+    // Tile the player walks to when harvesting, at the foot of the object
+    fn harvest_point(&self, objects: &ResourceObjects) -> Position {
+        if let Some(obj) = objects.get(self.resource_type.get_asset_name()) {
+            let (x, y) = obj.get_path_point(self.position.x as usize, self.position.y as usize);
+            Position::new(x as i32, y as i32)
+        } else {
+            self.position.clone()
+        }
+    }
+
+    fn to_saved(&self) -> NodeState {
+        NodeState {
+            position: self.position.clone(),
+            resource_type: self.resource_type.clone(),
+            craft_sentence: self.craft_sentence.clone(),
+            next_craft_sentence: self.next_craft_sentence.clone(),
+            current_input: self.current_input.clone(),
+            harvests_remaining: self.harvests_remaining,
+            max_harvests: self.max_harvests,
+            path: self.path.clone(),
+            word_seconds: self.word_start_time.map(|start| start.elapsed().as_secs_f32()),
+            mistakes: self.mistakes,
+        }
+    }
+
+    fn from_saved(node: &NodeState) -> Self {
+        // Half-typed input that doesn't fit the word is dropped rather than trusted
+        let typed = node.craft_sentence.starts_with(node.current_input.as_str()) && !node.current_input.is_empty();
+        Self {
+            position: node.position.clone(),
+            resource_type: node.resource_type.clone(),
+            craft_sentence: node.craft_sentence.clone(),
+            next_craft_sentence: node.next_craft_sentence.clone(),
+            current_input: if typed { node.current_input.clone() } else { String::new() },
+            harvests_remaining: node.harvests_remaining,
+            max_harvests: node.max_harvests,
+            path: if typed { node.path.clone() } else { Vec::new() },
+            // Carry on timing the word from where it was left
+            word_start_time: node.word_seconds
+                .filter(|_| typed)
+                .and_then(|seconds| Instant::now().checked_sub(Duration::from_secs_f32(seconds.max(0.0)))),
+            mistakes: node.mistakes,
+            reachable: true,
+        }
+    }
 }
 
 // Using shared ResourceType from resource_types.rs
 
+// Movement points earned per correct letter: one straight step across grass
+const MOVE_POINTS_PER_LETTER: u32 = 20;
+
+// How close (in steps) the player must be to a resource's harvest point to harvest it
+const HARVEST_RANGE: i32 = 2;
+
+// Particles thrown out by a harvest and by finishing a craft
+const HARVEST_PARTICLES: usize = 6;
+const CRAFT_SPARKS: usize = 10;
+
 struct Player {
     position: Position,
     path: Vec<Position>,
     target: Option<Position>,
+    move_points: u32,  // Unspent movement carried over to the next letter
     wood: u32,
     copper: u32,
 }
@@ -74,25 +177,63 @@ impl Player {
             position: Position::new(x, y),
             path: Vec::new(),
             target: None,
+            move_points: 0,
             wood: 0,
             copper: 0,
         }
     }
-    
-    fn move_along_path(&mut self) {
-        if !self.path.is_empty() {
-            self.position = self.path.remove(0);
+
+    // Spend one letter's worth of movement walking along a path. Roads let the
+    // player cover two tiles per letter, sand and shallows take several letters
+    fn advance(&mut self, path: &mut Vec<Position>, grid: &Grid) {
+        self.move_points += MOVE_POINTS_PER_LETTER;
+        while !path.is_empty() {
+            // The path was valid when it was found, so treat surprises as free steps
+            let cost = grid.step_cost(&self.position, &path[0]).unwrap_or(0);
+            if cost > self.move_points {
+                break;
+            }
+            self.move_points -= cost;
+            self.position = path.remove(0);
         }
+        if path.is_empty() {
+            self.move_points = 0;
+        }
+    }
+}
+
+// Screens over the game that take the keyboard until they're closed
+#[derive(Clone, Copy, PartialEq)]
+enum Overlay {
+    Achievements,
+    QuestLog,
+}
+
+// What quest objectives are checked against. Takes the fields rather than the
+// game so the quests themselves can still change while it's borrowed
+fn quest_context<'a>(
+    stats: &'a GameStats,
+    crafting: &'a CraftingManager,
+    islands: &'a IslandManager,
+    resources: &[Resource],
+) -> QuestContext<'a> {
+    QuestContext {
+        stats,
+        crafting,
+        islands,
+        typing: resources.iter().any(|r| !r.current_input.is_empty()),
     }
 }
 
 struct Game {
     player: Player,
     resources: Vec<Resource>,
+    island_nodes: HashMap<usize, Vec<Resource>>,  // Node state of islands we've sailed away from
     last_update: Instant,
     grid: Grid,
+    obstacles_dirty: bool,  // Resources changed since the grid's obstacle layer was built
     resource_objects: ResourceObjects,
-    floating_texts: FloatingTextManager,
+    effects: EffectsManager,
     upgrades: UpgradeManager,
     island_manager: IslandManager,
     crafting: CraftingManager,
@@ -103,66 +244,52 @@ struct Game {
     updater: Updater,
     pending_update: Option<VersionInfo>,
     coastline: Coastline,
+    combo: ComboTracker,
+    progression: PlayerLevel,
+    camera: Camera,
+    show_minimap: bool,
+    layout: LayoutManager,
+    movement_mode: MovementMode,
+    walker: Walker,
+    clock: WorldClock,
+    dirs: AppDirs,
+    profile: Profile,
+    last_save: SaveData,  // What was last loaded or saved, to check progress against
+    saving_blocked: bool,  // The save couldn't be loaded or a newer build wrote it, so leave it alone
+    last_save_error: Option<String>,  // Logged once, until a save works again or the error changes
+    idle: IdleManager,
+    away: Option<AwayReport>,  // "While you were away" summary, until the next key press
+    achievements: AchievementManager,
+    quests: QuestManager,
+    overlay: Option<Overlay>,
 }
 
 impl Game {
-    fn new() -> Self {
-        let save_manager = SaveManager::new();
-        let save_data = save_manager.load_game().unwrap_or_default();
-        
-        let mut rng = rand::thread_rng();
-        let word_list = WordList::new();
-        let mut resources = Vec::new();
-        let island_manager = IslandManager::new();
-        
-        // Start with half the max nodes
-        let current_island = island_manager.get_current_island();
-        let initial_nodes = current_island.max_nodes / 2;
-        
-        // Track positions for proper spacing
-        let mut existing_positions = Vec::new();
-        
-        // Spawn initial resources
-        for _ in 0..initial_nodes {
-            if let Some((x, y)) = island_manager.find_spawn_position(&existing_positions, 80, 24) {
-                existing_positions.push((x, y));
-                
-                // Create new resource
-                let resource_type = island_manager.get_random_resource_type();
-                let difficulty = match resource_type {
-                    ResourceType::Wood => WordDifficulty::Easy,
-                    ResourceType::Copper => WordDifficulty::Medium,
-                };
-                
-                let (min_harvests, max_harvests) = resource_type.get_base_harvests();
-                let max_harvests = rng.gen_range(min_harvests..=max_harvests);
-                
-                let word = word_list.get_random_word(difficulty).to_string();
-                let next_word = word_list.get_random_word(difficulty).to_string();
-                
-                let new_resource = Resource {
-                    position: Position::new(x, y),
-                    resource_type,
-                    craft_sentence: word,
-                    next_craft_sentence: next_word,
-                    current_input: String::new(),
-                    harvests_remaining: max_harvests,
-                    max_harvests,
-                    path: Vec::new(),
-                    word_start_time: None,
-                };
-                
-                resources.push(new_resource);
+    // `moved_save` is where an old single save was moved to this run, if anywhere
+    fn new(dirs: AppDirs, profile: Profile, moved_save: Option<PathBuf>) -> Self {
+        let mut save_manager = SaveManager::with_path(profile.save_file());
+        let settings = profile.load_settings();
+        let key_error = match InstallKey::load_or_create(&dirs.key_file()) {
+            Ok(key) => {
+                save_manager = save_manager.with_key(key);
+                None
             }
-        }
+            Err(e) => Some(e),
+        };
+
+        // A save that can't be loaded, e.g. from a newer version, is reported once the game is up
+        let (save_data, signature, load_error) = match save_manager.load_game_signed() {
+            Ok((save_data, signature)) => (save_data, signature, None),
+            Err(e) => (SaveData::default(), Signature::Valid, Some(e.to_string())),
+        };
         
-        let mut grid = Grid::new();
-        for resource in &resources {
-            grid.add_obstacle(resource.position.clone());
-        }
+        let word_list = WordList::new();
+        let mut island_manager = IslandManager::new();
+        island_manager.load_visited(&save_data.visited_islands);
         
-        // Start player in middle of screen
-        let mut player = Player::new(40, 12);
+        // Start player on land nearest the middle of the island
+        let (start_x, start_y) = island_manager.landing_position();
+        let mut player = Player::new(start_x, start_y);
         
         // Load saved data
         player.wood = save_data.player_wood;
@@ -177,39 +304,312 @@ impl Game {
         
         let mut game = Self {
             player,
-            resources,
+            resources: Vec::new(),
+            island_nodes: HashMap::new(),
             last_update: Instant::now(),
-            grid,
-            resource_objects: ResourceObjects::new(),
-            floating_texts: FloatingTextManager::new(),
+            grid: Grid::new(),
+            obstacles_dirty: true,
+            resource_objects: ResourceObjects::new(&dirs.art_dir()),
+            effects: EffectsManager::new(),
             upgrades: UpgradeManager::new(),
             island_manager,
             crafting,
             word_list,
             save_manager,
-            stats: save_data.stats,
+            stats: save_data.stats.clone(),
             show_debug_info: false,
             updater: Updater::new(),
             pending_update: None,
             coastline: Coastline::new(),
+            combo: ComboTracker::new(),
+            progression: PlayerLevel::new(save_data.player_level, save_data.player_xp),
+            camera: Camera::new(),
+            show_minimap: settings.show_minimap,
+            layout: LayoutManager::new(),
+            movement_mode: save_data.movement_mode,
+            walker: Walker::new(),
+            clock: WorldClock::new(),
+            dirs,
+            profile,
+            last_save: save_data.clone(),
+            saving_blocked: load_error.is_some(),
+            last_save_error: None,
+            idle: IdleManager::new(),
+            away: None,
+            achievements: AchievementManager::new(&save_data.achievements),
+            quests: QuestManager::new(save_data.quests.as_ref(), &save_data.stats),
+            overlay: None,
         };
         
-        // ... rest of initialization ...
+        // Recipes unlock by the level progression accepted, not the raw saved one
+        game.crafting.set_player_level(game.progression.level());
+
+        game.grid.set_tile_map(game.island_manager.get_tile_map());
+        game.grid.set_diagonal(true);
+        // Jump points only apply to uniform-cost terrain. Every island mixes roads,
+        // grass, forest, sand and shallows, so in practice the game runs weighted A*
+        game.grid.set_jump_points(true);
+
+        if let Some(error) = &load_error {
+            game.warn(format!("Not saving: {}", error));
+        }
+        if let Some(error) = key_error {
+            game.warn(format!("Saves won't be signed: {}", error));
+        }
+        if signature == Signature::Invalid {
+            game.warn("Save was changed outside the game or comes from another install".to_string());
+        }
+
+        if let Some(dir) = moved_save {
+            let message = format!("Save moved to {}", dir.display());
+            game.dirs.log(&message);
+            game.effects.add_styled_text(
+                message,
+                game.player.position.x as f32,
+                game.player.position.y as f32 - 4.0,
+                TextStyle::rising(Color::Cyan).with_lifetime(Duration::from_secs(6))
+            );
+        }
+
+        // Broken custom art falls back to the built-in assets, but say so
+        for error in game.resource_objects.load_errors() {
+            game.dirs.log(&format!("Art asset skipped: {}", error));
+        }
+        let art_errors = game.resource_objects.load_errors().len();
+        if art_errors > 0 {
+            game.effects.add_text(
+                format!("{} art asset(s) failed to load", art_errors),
+                game.player.position.x as f32,
+                game.player.position.y as f32 - 2.0,
+                Color::Red
+            );
+        }
+
+        // Carry on where the last session stopped, or start with half the max
+        // nodes when there's no world to go back to
+        let restored = save_data.world.as_ref().is_some_and(|world| game.restore_world(world));
+        if !restored {
+            let initial_nodes = game.island_manager.get_current_island().max_nodes / 2;
+            game.spawn_island_nodes(initial_nodes);
+        }
+
+        // Whatever was earned before achievements existed unlocks quietly
+        game.check_achievements();
+
+        // Workers kept going while the game was closed
+        if load_error.is_none() {
+            game.pay_offline_earnings(save_data.save_timestamp);
+        }
+        
         game
     }
+
+    // The save is written straight after paying out, so starting the game again
+    // after a crash can't pay for the same time twice
+    fn pay_offline_earnings(&mut self, saved_at: u64) {
+        let report = match AwayReport::compute(saved_at, unix_now(), &self.crafting.workers()) {
+            Some(report) => report,
+            None => return,
+        };
+        for (resource_type, amount) in &report.earned {
+            self.add_generated(resource_type.clone(), *amount);
+        }
+        if let Err(e) = self.save_game() {
+            self.dirs.log(&format!("Couldn't save offline earnings: {}", e));
+        }
+        if report.worth_showing() {
+            self.away = Some(report);
+        }
+    }
+
+    // Achievements newly earned by progress so far
+    fn check_achievements(&mut self) -> Vec<&'static Achievement> {
+        let visited = self.island_manager.visited_names();
+        let progress = Progress {
+            stats: &self.stats,
+            completed_items: self.crafting.get_completed_items(),
+            visited_islands: &visited,
+            island_count: self.island_manager.get_islands().len(),
+        };
+        self.achievements.check(&progress)
+    }
+
+    fn announce_achievements(&mut self, unlocked: Vec<&'static Achievement>) {
+        for achievement in unlocked {
+            self.effects.add_styled_text(
+                format!("Achievement: {}!", achievement.name),
+                self.player.position.x as f32,
+                self.player.position.y as f32 - 3.0,
+                TextStyle::rising(Color::LightYellow).with_lifetime(Duration::from_secs(3))
+            );
+        }
+    }
+
+    // The away summary, achievements list or quest log is showing and takes the
+    // next key. None of them are drawn while the terminal is too small
+    fn screen_open(&self) -> bool {
+        self.layout.mode() != LayoutMode::TooSmall && (self.away.is_some() || self.overlay.is_some())
+    }
+
+    fn update_quests(&mut self) {
+        let context = quest_context(&self.stats, &self.crafting, &self.island_manager, &self.resources);
+        for quest in self.quests.update(&context) {
+            self.complete_quest(quest);
+        }
+    }
+
+    fn complete_quest(&mut self, quest: &'static Quest) {
+        self.effects.add_styled_text(
+            format!("Quest complete: {}!", quest.title),
+            self.player.position.x as f32,
+            self.player.position.y as f32 - 4.0,
+            TextStyle::rising(Color::LightGreen).with_lifetime(Duration::from_secs(3))
+        );
+        let reward = &quest.reward;
+        self.player.wood += reward.wood;
+        self.player.copper += reward.copper;
+        if reward.wood > 0 || reward.copper > 0 {
+            self.effects.add_text(
+                format!("+{} Wood +{} Copper", reward.wood, reward.copper),
+                self.player.position.x as f32,
+                self.player.position.y as f32 - 2.0,
+                Color::LightYellow
+            );
+        }
+        self.award_xp(reward.xp);
+    }
+
+    fn add_generated(&mut self, resource_type: ResourceType, amount: u32) {
+        match resource_type {
+            ResourceType::Wood => self.player.wood += amount,
+            ResourceType::Copper => self.player.copper += amount,
+        }
+        self.stats.add_resource_generated(resource_type, amount);
+    }
+
+    // Snapshot of the world for the save file
+    fn world_state(&self) -> WorldState {
+        let islands = self.island_manager.get_islands();
+        let mut island_nodes: Vec<IslandNodes> = self.island_nodes
+            .iter()
+            .filter_map(|(index, nodes)| Some(IslandNodes {
+                island: islands.get(*index)?.name.clone(),
+                nodes: nodes.iter().map(Resource::to_saved).collect(),
+            }))
+            .collect();
+        island_nodes.push(IslandNodes {
+            island: self.island_manager.get_current_island().name.clone(),
+            nodes: self.resources.iter().map(Resource::to_saved).collect(),
+        });
+        island_nodes.sort_by(|a, b| a.island.cmp(&b.island));
+
+        WorldState {
+            current_island: self.island_manager.get_current_island().name.clone(),
+            player: PlayerState {
+                position: self.player.position.clone(),
+                path: self.player.path.clone(),
+                target: self.player.target.clone(),
+                move_points: self.player.move_points,
+                walking_to: self.walker.current().cloned(),
+                walk_queue: self.walker.queued_harvests().cloned().collect(),
+            },
+            islands: island_nodes,
+            recipe_inputs: self.crafting.inputs_in_progress(),
+            voyage_inputs: self.island_manager.voyage_inputs_in_progress(),
+        }
+    }
+
+    // Put the world back as it was saved. Returns false if the saved island no
+    // longer exists, in which case the caller starts the island afresh
+    fn restore_world(&mut self, world: &WorldState) -> bool {
+        let current = match self.island_manager.index_of(&world.current_island) {
+            Some(index) => index,
+            None => return false,
+        };
+        if current != self.island_manager.current_index() {
+            self.island_manager.travel_to(current);
+            self.grid.set_tile_map(self.island_manager.get_tile_map());
+        }
+
+        for saved in &world.islands {
+            let index = match self.island_manager.index_of(&saved.island) {
+                Some(index) => index,
+                None => continue,
+            };
+            let nodes: Vec<Resource> = saved.nodes
+                .iter()
+                .filter(|node| node.harvests_remaining > 0)
+                .map(Resource::from_saved)
+                .collect();
+            if index == current {
+                self.resources = nodes;
+            } else {
+                self.island_nodes.insert(index, nodes);
+            }
+        }
+        if self.resources.is_empty() {
+            let initial_nodes = self.island_manager.get_current_island().max_nodes / 2;
+            self.spawn_island_nodes(initial_nodes);
+        }
+        self.obstacles_dirty = true;
+
+        // The terrain may have changed since the save, so make sure the player
+        // is standing somewhere they can walk
+        let tile_map = self.island_manager.get_tile_map();
+        let saved = &world.player;
+        let (x, y) = tile_map.nearest_walkable(saved.position.x, saved.position.y)
+            .unwrap_or_else(|| self.island_manager.landing_position());
+        self.player.position = Position::new(x, y);
+        self.camera.center_on(x, y, tile_map.width(), tile_map.height());
+        if self.player.position == saved.position {
+            self.player.path = saved.path.clone();
+            self.player.target = saved.target.clone();
+            self.player.move_points = saved.move_points;
+            if self.movement_mode == MovementMode::Walking {
+                self.walker.restore(saved.walking_to.clone(), saved.walk_queue.clone());
+            }
+        }
+
+        self.crafting.load_inputs(&world.recipe_inputs);
+        self.island_manager.load_voyage_inputs(&world.voyage_inputs);
+        true
+    }
     
     fn update(&mut self) {
+        // Keep obstacles and the unreachable markers current once resources change
+        self.refresh_obstacles();
+        self.update_walking();
+
+        // Announce sunrise, nightfall and weather changes
+        if let Some(message) = self.clock.update() {
+            self.effects.add_text(
+                message.to_string(),
+                self.player.position.x as f32,
+                self.player.position.y as f32 - 2.0,
+                Color::Cyan
+            );
+        }
+
         let now = Instant::now();
         if now.duration_since(self.last_update) >= Duration::from_millis(50) {
-            // Update floating texts
-            self.floating_texts.update();
+            // Drop finished texts and particles
+            self.effects.update();
             self.last_update = now;
         }
 
+        // Workers gather in the background
+        for (resource_type, amount) in self.idle.tick(&self.crafting.workers()) {
+            self.add_generated(resource_type, amount);
+        }
+
+        let unlocked = self.check_achievements();
+        self.announce_achievements(unlocked);
+        self.update_quests();
+
         // Auto-save check
-        if self.save_manager.should_auto_save() {
+        if !self.saving_blocked && self.save_manager.should_auto_save() {
             if let Err(e) = self.save_game() {
-                // eprintln!("Auto-save failed: {}", e);
+                self.auto_save_failed(e.as_ref());
             }
         }
 
@@ -220,47 +620,114 @@ impl Game {
             }
         }
 
-        self.coastline.update();
+        self.coastline.update(&self.clock);
     }
     
-    fn set_player_target(&mut self, target: Position) {
-        // Update grid with current obstacles
-        self.grid.clear_obstacles();
-        
-        // Add obstacles for each resource's ASCII art area, except the target
-        for resource in &self.resources {
-            let obj = match resource.resource_type {
-                ResourceType::Wood => self.resource_objects.get("tree"),
-                ResourceType::Copper => self.resource_objects.get("copper"),
-            };
-            
-            if let Some(obj) = obj {
-                let (w, h) = obj.dimensions();
-                let rx = resource.position.x as usize;
-                let ry = resource.position.y as usize;
-                
-                // Get the path point for this resource
-                let (path_x, path_y) = obj.get_path_point(rx, ry);
-                let path_pos = Position::new(path_x as i32, path_y as i32);
-                
-                // Add obstacles for the entire object area except the path point
-                for dy in 0..h {
-                    for dx in 0..w {
-                        let pos = Position::new((rx + dx) as i32, (ry + dy) as i32);
-                        if pos != path_pos {  // Don't block the path point
-                            self.grid.add_obstacle(pos);
-                        }
+    // Walking mode: move along the current path over time, harvest on arrival
+    // and then head off to the next queued resource
+    fn update_walking(&mut self) {
+        let speed = self.crafting.get_walk_speed_multiplier();
+        if self.walker.tick(&mut self.player.position, &mut self.player.path, &self.grid, speed) {
+            if let Some(harvest) = self.walker.finish() {
+                self.player.target = None;
+                // The resource may have been depleted or left behind since the word was typed
+                if let Some(idx) = self.resources.iter().position(|r| r.position == harvest.resource) {
+                    let target = self.resources[idx].harvest_point(&self.resource_objects);
+                    if self.player.position.manhattan_distance(&target) <= HARVEST_RANGE {
+                        self.harvest_at(idx, harvest.performance.as_ref());
                     }
                 }
             }
         }
-        
+
+        if !self.walker.is_walking() {
+            self.start_next_walk();
+        }
+    }
+
+    fn start_next_walk(&mut self) {
+        self.refresh_obstacles();
+        while let Some(harvest) = self.walker.next_queued() {
+            let target = match self.resources.iter().find(|r| r.position == harvest.resource) {
+                Some(resource) => resource.harvest_point(&self.resource_objects),
+                None => continue,
+            };
+
+            match self.grid.find_path_near(self.player.position.clone(), target.clone(), HARVEST_RANGE) {
+                Some(path) => {
+                    self.player.path = path;
+                    self.player.target = Some(target);
+                    self.walker.begin(harvest);
+                    return;
+                }
+                None => {
+                    self.effects.add_styled_text(
+                        "Can't reach!".to_string(),
+                        harvest.resource.x as f32,
+                        harvest.resource.y as f32 - 2.0,
+                        TextStyle::sinking(Color::Red)
+                    );
+                }
+            }
+        }
+    }
+
+    fn set_movement_mode(&mut self, mode: MovementMode) {
+        self.movement_mode = mode;
+
+        // Drop any walk or half-typed route from the other mode
+        self.walker.clear();
+        self.player.path.clear();
+        self.player.target = None;
+        for resource in &mut self.resources {
+            resource.path.clear();
+        }
+
+        self.effects.add_text(
+            mode.label().to_string(),
+            self.player.position.x as f32,
+            self.player.position.y as f32 - 1.0,
+            Color::Cyan
+        );
+    }
+
+    fn set_player_target(&mut self, target: Position) {
+        self.refresh_obstacles();
+
         // Find path
         if let Some(path) = self.grid.find_path(self.player.position.clone(), target.clone()) {
             self.player.path = path;  // Keep the full path including target
             self.player.target = Some(target);
         }
     }
+
+    // Rebuild the grid's obstacle layer, but only after resources have changed
+    fn refresh_obstacles(&mut self) {
+        if !self.obstacles_dirty {
+            return;
+        }
+        self.grid.clear_obstacles();
+
+        // Add obstacles from each resource's collision mask. Path points stay open
+        // so every resource can still be reached
+        for resource in &self.resources {
+            if let Some(obj) = self.resource_objects.get(resource.resource_type.get_asset_name()) {
+                let rx = resource.position.x as usize;
+                let ry = resource.position.y as usize;
+                for (x, y) in obj.collision_tiles(rx, ry) {
+                    self.grid.add_obstacle(Position::new(x as i32, y as i32));
+                }
+            }
+        }
+        self.obstacles_dirty = false;
+
+        // Blocking changed, so work out again which resources the player can get to
+        self.grid.update_reachable(&self.player.position);
+        for resource in &mut self.resources {
+            let target = resource.harvest_point(&self.resource_objects);
+            resource.reachable = self.grid.is_reachable_within(&target, HARVEST_RANGE);
+        }
+    }
     
     fn try_spawn_resource(&mut self) {
         let current_island = self.island_manager.get_current_island();
@@ -282,48 +749,61 @@ impl Game {
         }
     }
 
-    fn spawn_new_resource(&mut self) {
+    fn create_resource(&self, resource_type: ResourceType, x: i32, y: i32) -> Resource {
         let mut rng = rand::thread_rng();
+        let difficulty = self.word_difficulty(&resource_type);
+        
+        let (min_harvests, max_harvests) = resource_type.get_base_harvests();
+        let max_harvests = rng.gen_range(min_harvests..=max_harvests);
         
+        let word = self.word_list.get_random_word(difficulty).to_string();
+        let next_word = self.word_list.get_random_word(self.word_difficulty(&resource_type)).to_string();
+        
+        Resource {
+            position: Position::new(x, y),
+            resource_type,
+            craft_sentence: word,
+            next_craft_sentence: next_word,
+            current_input: String::new(),
+            harvests_remaining: max_harvests,
+            max_harvests,
+            path: Vec::new(),
+            word_start_time: None,
+            mistakes: 0,
+            reachable: true,
+        }
+    }
+
+    // Footprint of a resource's art, used when looking for room to spawn it
+    fn resource_size(&self, resource_type: &ResourceType) -> (i32, i32) {
+        match self.resource_objects.get(resource_type.get_asset_name()) {
+            Some(obj) => {
+                let (w, h) = obj.dimensions();
+                (w as i32, h as i32)
+            }
+            None => (4, 4),
+        }
+    }
+
+    fn spawn_new_resource(&mut self) {
         // Get existing positions
         let existing_positions: Vec<(i32, i32)> = self.resources
             .iter()
             .map(|r| (r.position.x, r.position.y))
             .collect();
 
-        // Try to find a spawn position
-        if let Some((x, y)) = self.island_manager.find_spawn_position(&existing_positions, 80, 24) {
-            // Create new resource
-            let resource_type = self.island_manager.get_random_resource_type();
-            let difficulty = match resource_type {
-                ResourceType::Wood => WordDifficulty::Easy,
-                ResourceType::Copper => WordDifficulty::Medium,
-            };
-            
-            let (min_harvests, max_harvests) = resource_type.get_base_harvests();
-            let max_harvests = rng.gen_range(min_harvests..=max_harvests);
-            
-            let word = self.word_list.get_random_word(difficulty).to_string();
-            let next_word = self.word_list.get_random_word(difficulty).to_string();
-            
-            let new_resource = Resource {
-                position: Position::new(x, y),
-                resource_type,
-                craft_sentence: word,
-                next_craft_sentence: next_word,
-                current_input: String::new(),
-                harvests_remaining: max_harvests,
-                max_harvests,
-                path: Vec::new(),
-                word_start_time: None,
-            };
+        // Try to find a spawn position on tiles that suit the resource
+        let resource_type = self.island_manager.get_random_resource_type(&self.clock);
+        let size = self.resource_size(&resource_type);
+        if let Some((x, y)) = self.island_manager.find_spawn_position(&resource_type, size, &existing_positions) {
+            let new_resource = self.create_resource(resource_type, x, y);
             
             // Add the resource and update the grid
-            self.grid.add_obstacle(new_resource.position.clone());
             self.resources.push(new_resource);
+            self.obstacles_dirty = true;
             
             // Show spawn notification
-            self.floating_texts.add_text(
+            self.effects.add_text(
                 "New Resource!".to_string(),
                 x as f32,
                 y as f32 - 1.0,
@@ -332,130 +812,213 @@ impl Game {
         }
     }
 
-    fn harvest_resource(&mut self) {
-        // First find the index of the resource to harvest
-        let mut harvest_idx = None;
-        let mut harvest_amount = 0;
-        let mut harvest_text = String::new();
-        let mut harvest_color = Color::White;
+    // Spawn a batch of nodes at once, e.g. on arrival or after clearing an island
+    fn spawn_island_nodes(&mut self, count: u32) {
+        let mut existing_positions: Vec<(i32, i32)> = self.resources
+            .iter()
+            .map(|r| (r.position.x, r.position.y))
+            .collect();
 
-        // Find the resource to harvest
-        for (idx, resource) in self.resources.iter().enumerate() {
-            let target_pos = if let Some(obj) = match resource.resource_type {
-                ResourceType::Wood => self.resource_objects.get("tree"),
-                ResourceType::Copper => self.resource_objects.get("copper"),
-            } {
-                let (x, y) = obj.get_path_point(resource.position.x as usize, resource.position.y as usize);
-                Position::new(x as i32, y as i32)
-            } else {
-                resource.position.clone()
-            };
+        for _ in 0..count {
+            let resource_type = self.island_manager.get_random_resource_type(&self.clock);
+            let size = self.resource_size(&resource_type);
+            if let Some((x, y)) = self.island_manager.find_spawn_position(&resource_type, size, &existing_positions) {
+                existing_positions.push((x, y));
+                let new_resource = self.create_resource(resource_type, x, y);
+                self.resources.push(new_resource);
+                self.obstacles_dirty = true;
+            }
+        }
+    }
 
-            let distance = self.player.position.manhattan_distance(&target_pos);
-            if distance <= 2 && resource.current_input == resource.craft_sentence {
-                // Calculate harvest amount and text
-                let (amount, text, color) = match resource.resource_type {
-                    ResourceType::Wood => {
-                        let multiplier = self.crafting.get_multiplier(&ResourceType::Wood);
-                        let amount = (multiplier as u32).max(1);
-                        self.player.wood += amount;
-                        self.stats.add_resource_harvested(ResourceType::Wood, amount);
-                        (amount, "Wood".to_string(), ResourceType::Wood.get_color())
-                    },
-                    ResourceType::Copper => {
-                        let multiplier = self.crafting.get_multiplier(&ResourceType::Copper);
-                        let amount = (multiplier as u32).max(1);
-                        self.player.copper += amount;
-                        self.stats.add_resource_harvested(ResourceType::Copper, amount);
-                        (amount, "Copper".to_string(), ResourceType::Copper.get_color())
-                    },
-                };
-                harvest_idx = Some(idx);
-                harvest_amount = amount;
-                harvest_text = text;
-                harvest_color = color;
-                break;
+    fn travel_to_island(&mut self, index: usize) {
+        let from = self.island_manager.current_index();
+        let first_visit = !self.island_manager.has_visited(index);
+        if !self.island_manager.travel_to(index) {
+            return;
+        }
+
+        // Leave the current island's nodes exactly as they are for when we come back
+        let departing = std::mem::take(&mut self.resources);
+        self.island_nodes.insert(from, departing);
+        self.grid.set_tile_map(self.island_manager.get_tile_map());
+        self.obstacles_dirty = true;
+
+        // Restore the destination's nodes, or populate it on first arrival
+        match self.island_nodes.remove(&index) {
+            Some(nodes) => self.resources = nodes,
+            None => {
+                let initial_nodes = self.island_manager.get_current_island().max_nodes / 2;
+                self.spawn_island_nodes(initial_nodes);
             }
         }
+        for resource in &mut self.resources {
+            resource.current_input.clear();
+            resource.path.clear();
+            resource.word_start_time = None;
+        }
+
+        // Step off the boat in the middle of the island
+        let (land_x, land_y) = self.island_manager.landing_position();
+        self.player.position = Position::new(land_x, land_y);
+        let tile_map = self.island_manager.get_tile_map();
+        self.camera.center_on(land_x, land_y, tile_map.width(), tile_map.height());
+        self.player.path.clear();
+        self.player.target = None;
+        self.walker.clear();
+
+        self.effects.add_text(
+            format!("Arrived at {}!", self.island_manager.get_current_island().name),
+            self.player.position.x as f32,
+            self.player.position.y as f32 - 1.0,
+            Color::Cyan
+        );
+
+        // Discovering a new island is worth a chunk of XP
+        if first_visit {
+            self.award_xp(XP_PER_VOYAGE);
+        }
+    }
+
+    fn harvest_resource(&mut self, performance: Option<&WordPerformance>) {
+        // Find the resource to harvest: a finished word the player is standing next to
+        let harvest_idx = self.resources.iter().position(|resource| {
+            let target_pos = resource.harvest_point(&self.resource_objects);
+            let distance = self.player.position.manhattan_distance(&target_pos);
+            distance <= HARVEST_RANGE && resource.current_input == resource.craft_sentence
+        });
 
-        // Process the harvest if we found a resource
         if let Some(idx) = harvest_idx {
-            // Show floating text
-            self.floating_texts.add_text(
-                format!("+{} {}", harvest_amount, harvest_text),
-                self.player.position.x as f32,
-                self.player.position.y as f32 - 1.0,
-                harvest_color
-            );
+            self.harvest_at(idx, performance);
+        }
+    }
 
-            // Update the resource
-            if let Some(resource) = self.resources.get_mut(idx) {
-                resource.harvests_remaining = resource.harvests_remaining.saturating_sub(1);
-                resource.current_input.clear();
-
-                // Check if this was the last node and it's depleted
-                if resource.harvests_remaining == 0 {
-                    // Remove depleted resources
-                    self.resources.retain(|r| r.harvests_remaining > 0);
-
-                    // If no resources left, respawn max_nodes
-                    if self.resources.is_empty() {
-                        let current_island = self.island_manager.get_current_island();
-                        self.floating_texts.add_text(
-                            "CLEAR! Respawning nodes...".to_string(),
-                            40.0, // Center of screen
-                            12.0,
-                            Color::Cyan
-                        );
+    fn harvest_at(&mut self, idx: usize, performance: Option<&WordPerformance>) {
+        let (resource_type, origin) = match self.resources.get(idx) {
+            Some(resource) => (resource.resource_type.clone(), resource.position.clone()),
+            None => return,
+        };
 
-                        // Spawn max_nodes new resources
-                        let mut rng = rand::thread_rng();
-                        let mut existing_positions = Vec::new();
-                        for _ in 0..current_island.max_nodes {
-                            if let Some((x, y)) = self.island_manager.find_spawn_position(&existing_positions, 80, 24) {
-                                existing_positions.push((x, y));
-                                
-                                // Create new resource
-                                let resource_type = self.island_manager.get_random_resource_type();
-                                let difficulty = match resource_type {
-                                    ResourceType::Wood => WordDifficulty::Easy,
-                                    ResourceType::Copper => WordDifficulty::Medium,
-                                };
-                                
-                                let (min_harvests, max_harvests) = resource_type.get_base_harvests();
-                                let max_harvests = rng.gen_range(min_harvests..=max_harvests);
-                                
-                                let word = self.word_list.get_random_word(difficulty).to_string();
-                                let next_word = self.word_list.get_random_word(difficulty).to_string();
-                                
-                                let new_resource = Resource {
-                                    position: Position::new(x, y),
-                                    resource_type,
-                                    craft_sentence: word,
-                                    next_craft_sentence: next_word,
-                                    current_input: String::new(),
-                                    harvests_remaining: max_harvests,
-                                    max_harvests,
-                                    path: Vec::new(),
-                                    word_start_time: None,
-                                };
-                                
-                                self.resources.push(new_resource);
-                            }
-                        }
-                    }
-                }
+        // Calculate harvest amount and text
+        let (harvest_amount, harvest_text, harvest_color) = match resource_type {
+            ResourceType::Wood => {
+                let multiplier = self.crafting.get_multiplier(&ResourceType::Wood);
+                let base_amount = (multiplier as u32).max(1) + self.island_manager.yield_bonus(&ResourceType::Wood);
+                let amount = performance.map_or(base_amount, |p| p.apply(base_amount));
+                self.player.wood += amount;
+                self.stats.add_resource_harvested(ResourceType::Wood, amount);
+                (amount, "Wood".to_string(), ResourceType::Wood.get_color())
+            },
+            ResourceType::Copper => {
+                let multiplier = self.crafting.get_multiplier(&ResourceType::Copper);
+                let base_amount = (multiplier as u32).max(1) + self.island_manager.yield_bonus(&ResourceType::Copper);
+                let amount = performance.map_or(base_amount, |p| p.apply(base_amount));
+                self.player.copper += amount;
+                self.stats.add_resource_harvested(ResourceType::Copper, amount);
+                (amount, "Copper".to_string(), ResourceType::Copper.get_color())
+            },
+        };
+
+        // Chips or sparks fly off the middle of the node
+        let (width, height) = self.resource_size(&resource_type);
+        let particles = match resource_type {
+            ResourceType::Wood => ParticleKind::WoodChips,
+            ResourceType::Copper => ParticleKind::Sparks,
+        };
+        self.effects.burst(
+            particles,
+            (origin.x + width / 2) as f32,
+            (origin.y + height / 2) as f32,
+            HARVEST_PARTICLES
+        );
+
+        // Show floating text
+        self.effects.add_text(
+            format!("+{} {}", harvest_amount, harvest_text),
+            self.player.position.x as f32,
+            self.player.position.y as f32 - 1.0,
+            harvest_color
+        );
+
+        // Call out speed bonuses and combo milestones
+        if let Some(performance) = performance {
+            if performance.speed_bonus > 0.0 {
+                self.effects.add_styled_text(
+                    format!("Fast! {:.0} WPM", performance.wpm),
+                    self.player.position.x as f32,
+                    self.player.position.y as f32 - 2.0,
+                    TextStyle::hopping(Color::Magenta, 3.0)
+                );
+            } else if performance.combo > 0 && performance.combo % 5 == 0 {
+                self.effects.add_styled_text(
+                    format!("Combo x{}!", performance.combo),
+                    self.player.position.x as f32,
+                    self.player.position.y as f32 - 2.0,
+                    TextStyle::hopping(Color::Magenta, 3.0)
+                );
             }
+        }
+
+        self.award_xp(XP_PER_HARVEST);
 
-            // Try to spawn a new resource
-            self.try_spawn_resource();
+        // Update the resource
+        if let Some(resource) = self.resources.get_mut(idx) {
+            resource.harvests_remaining = resource.harvests_remaining.saturating_sub(1);
+
+            // Check if this was the last node and it's depleted
+            if resource.harvests_remaining == 0 {
+                // Remove depleted resources
+                self.resources.retain(|r| r.harvests_remaining > 0);
+                self.obstacles_dirty = true;
+
+                // If no resources left, respawn max_nodes
+                if self.resources.is_empty() {
+                    let current_island = self.island_manager.get_current_island();
+                    self.effects.add_text(
+                        "CLEAR! Respawning nodes...".to_string(),
+                        self.player.position.x as f32,
+                        self.player.position.y as f32 - 2.0,
+                        Color::Cyan
+                    );
+
+                    // Spawn max_nodes new resources
+                    let max_nodes = current_island.max_nodes;
+                    self.spawn_island_nodes(max_nodes);
+                }
+            }
         }
+
+        // Try to spawn a new resource
+        self.try_spawn_resource();
     }
     
     fn handle_key(&mut self, key: KeyEvent) -> Option<VersionInfo> {
+        // Only the too-small notice is drawn, so keys would act on things the
+        // player can't see. Esc still quits, before it gets here
+        if self.layout.mode() == LayoutMode::TooSmall {
+            return None;
+        }
+
         // Stop showing debug info after first key press
         self.show_debug_info = false;
 
+        // The first key after the away summary only closes it
+        if self.away.take().is_some() {
+            return None;
+        }
+
+        // The achievements list and quest log stay up until they're closed,
+        // or the other one's key swaps it in
+        if let Some(overlay) = self.overlay {
+            self.overlay = match key.code {
+                KeyCode::F(3) if overlay != Overlay::Achievements => Some(Overlay::Achievements),
+                KeyCode::F(4) if overlay != Overlay::QuestLog => Some(Overlay::QuestLog),
+                KeyCode::F(3) | KeyCode::F(4) | KeyCode::Enter | KeyCode::Esc => None,
+                _ => Some(overlay),
+            };
+            return None;
+        }
+
         match key.code {
             KeyCode::Char('u') if self.pending_update.is_some() => {
                 // Clone version info before any mutable borrow
@@ -475,6 +1038,18 @@ impl Game {
                     }
                 }
             }
+            KeyCode::Tab => {
+                self.show_minimap = !self.show_minimap;
+                let settings = ProfileSettings { show_minimap: self.show_minimap };
+                if let Err(e) = self.profile.save_settings(&settings) {
+                    self.dirs.log(&format!("Couldn't save settings: {}", e));
+                }
+            }
+            KeyCode::F(2) => {
+                self.set_movement_mode(self.movement_mode.toggled());
+            }
+            KeyCode::F(3) => self.overlay = Some(Overlay::Achievements),
+            KeyCode::F(4) => self.overlay = Some(Overlay::QuestLog),
             KeyCode::Char(c) => {
                 // Handle crafting input - check all recipes simultaneously
                 let mut crafting_completed = false;
@@ -489,6 +1064,7 @@ impl Game {
                             // Check if crafting is complete
                             if let Some((recipe, costs)) = self.crafting.craft_item(recipe_idx) {
                                 self.stats.add_successful_craft();
+                                self.award_xp(XP_PER_CRAFT);
                                 
                                 // Deduct resources
                                 for (resource_type, amount) in costs {
@@ -499,16 +1075,22 @@ impl Game {
                                 }
 
                                 // Show crafting success message
-                                self.floating_texts.add_text(
+                                self.effects.add_styled_text(
                                     format!("Crafted {}!", recipe.name),
                                     self.player.position.x as f32,
                                     self.player.position.y as f32 - 1.0,
-                                    Color::Yellow
+                                    TextStyle::rising(Color::Yellow).with_lifetime(Duration::from_millis(1500))
+                                );
+                                self.effects.burst(
+                                    ParticleKind::Sparks,
+                                    self.player.position.x as f32,
+                                    self.player.position.y as f32,
+                                    CRAFT_SPARKS
                                 );
 
                                 // If this was the workbench, show unlock message
                                 if recipe_idx == 0 {
-                                    self.floating_texts.add_text(
+                                    self.effects.add_text(
                                         "New recipes unlocked!".to_string(),
                                         self.player.position.x as f32,
                                         self.player.position.y as f32 - 2.0,
@@ -542,26 +1124,40 @@ impl Game {
                     return None;
                 }
 
+                // Voyage sentences work like recipes: type one fully to sail there
+                if self.crafting.has_item("Boat") {
+                    let mut any_voyage_progress = false;
+                    let mut destination = None;
+                    for island_idx in 0..self.island_manager.get_islands().len() {
+                        if self.island_manager.can_sail_to(island_idx, true, self.progression.level()) &&
+                           self.island_manager.handle_voyage_input(island_idx, c) {
+                            any_voyage_progress = true;
+                            if self.island_manager.is_voyage_complete(island_idx) {
+                                destination = Some(island_idx);
+                            }
+                        }
+                    }
+
+                    if let Some(island_idx) = destination {
+                        self.travel_to_island(island_idx);
+                        return None;
+                    }
+                    if any_voyage_progress {
+                        return None;
+                    }
+                }
+
                 // If not crafting, handle resource gathering input
                 let mut should_harvest = false;
                 let mut completed_word_idx = None;
                 let mut word_completed = false;
+                let mut word_performance = None;
+                let mut word_event = None;
+                let mut word_xp = 0;
+                let mut walk_to = None;
 
-                // First collect all resource positions and their obstacles
-                let mut resource_obstacles = Vec::new();
-                for resource in &self.resources {
-                    let obj = match resource.resource_type {
-                        ResourceType::Wood => self.resource_objects.get("tree"),
-                        ResourceType::Copper => self.resource_objects.get("copper"),
-                    };
-                    
-                    if let Some(obj) = obj {
-                        let (w, h) = obj.dimensions();
-                        let rx = resource.position.x as usize;
-                        let ry = resource.position.y as usize;
-                        resource_obstacles.push((resource.position.clone(), (rx, ry, w, h)));
-                    }
-                }
+                // Resource objects block movement; the layer is cached until resources change
+                self.refresh_obstacles();
 
                 // Process each word independently
                 for (resource_idx, resource) in self.resources.iter_mut().enumerate() {
@@ -576,41 +1172,30 @@ impl Game {
                             resource.current_input.push(c);
                             resource.word_start_time = Some(Instant::now());
                             
-                            // Calculate initial path
-                            let target_pos = if let Some(obj) = match resource.resource_type {
-                                ResourceType::Wood => self.resource_objects.get("tree"),
-                                ResourceType::Copper => self.resource_objects.get("copper"),
-                            } {
-                                let (x, y) = obj.get_path_point(resource.position.x as usize, resource.position.y as usize);
-                                Position::new(x as i32, y as i32)
-                            } else {
-                                resource.position.clone()
-                            };
-                            
-                            // Clear and rebuild grid obstacles
-                            self.grid.clear_obstacles();
-                            for (pos, (rx, ry, w, h)) in &resource_obstacles {
-                                if *pos != resource.position {  // Don't block target
-                                    // Add obstacles for the object area
-                                    for dy in 0..*h {
-                                        for dx in 0..*w {
-                                            let obstacle_pos = Position::new((*rx + dx) as i32, (*ry + dy) as i32);
-                                            if obstacle_pos != target_pos {  // Don't block the actual target point
-                                                self.grid.add_obstacle(obstacle_pos);
-                                            }
-                                        }
+                            // In walking mode the player only sets off once the word is finished
+                            if self.movement_mode == MovementMode::Stepwise {
+                                // Calculate initial path
+                                let target_pos = resource.harvest_point(&self.resource_objects);
+
+                                // Walk to the harvest point, or as close as the terrain allows
+                                match self.grid.find_path_near(self.player.position.clone(), target_pos.clone(), HARVEST_RANGE) {
+                                    Some(path) => {
+                                        resource.path = path;  // Store path in the resource
+                                        self.player.target = Some(target_pos);
+                                    }
+                                    None => {
+                                        self.effects.add_styled_text(
+                                            "Can't reach!".to_string(),
+                                            resource.position.x as f32,
+                                            resource.position.y as f32 - 2.0,
+                                            TextStyle::sinking(Color::Red)
+                                        );
                                     }
                                 }
-                            }
 
-                            if let Some(path) = self.grid.find_path(self.player.position.clone(), target_pos.clone()) {
-                                resource.path = path;  // Store path in the resource
-                                self.player.target = Some(target_pos);
-                            }
-
-                            // Move first step
-                            if !resource.path.is_empty() {
-                                self.player.position = resource.path.remove(0);
+                                // Move first step
+                                self.player.move_points = 0;
+                                self.player.advance(&mut resource.path, &self.grid);
                             }
                         }
                     }
@@ -621,42 +1206,52 @@ impl Game {
                             // Continue the word
                             resource.current_input.push(c);
 
-                            // Move one step
-                            if !resource.path.is_empty() {
-                                self.player.position = resource.path.remove(0);
+                            // Move along the path as far as the terrain allows
+                            if self.movement_mode == MovementMode::Stepwise {
+                                self.player.advance(&mut resource.path, &self.grid);
                             }
 
                             // Check if word is complete
                             if resource.current_input == *target_word {
                                 completed_word_idx = Some(resource_idx);
                                 word_completed = true;
+                                word_xp = target_word.len() as u32 * XP_PER_WORD_CHAR;
                                 
-                                // Track word completion stats
+                                // Track word completion stats and typing performance
                                 if let Some(start_time) = resource.word_start_time {
                                     let time_taken = start_time.elapsed().as_secs_f32();
                                     self.stats.add_word_completed(target_word.len() as u32, time_taken);
+                                    let performance = self.combo.record_word(
+                                        target_word.len() as u32,
+                                        time_taken,
+                                        resource.mistakes,
+                                    );
+                                    word_event = Some(GameEvent::WordCompleted {
+                                        length: target_word.len() as u32,
+                                        wpm: performance.wpm,
+                                    });
+                                    word_performance = Some(performance);
                                 }
                                 resource.word_start_time = None;
                                 
-                                // Get the target position
-                                let target_pos = if let Some(obj) = match resource.resource_type {
-                                    ResourceType::Wood => self.resource_objects.get("tree"),
-                                    ResourceType::Copper => self.resource_objects.get("copper"),
-                                } {
-                                    let (x, y) = obj.get_path_point(resource.position.x as usize, resource.position.y as usize);
-                                    Position::new(x as i32, y as i32)
+                                if self.movement_mode == MovementMode::Walking {
+                                    walk_to = Some(resource.position.clone());
                                 } else {
-                                    resource.position.clone()
-                                };
+                                    // Get the target position
+                                    let target_pos = resource.harvest_point(&self.resource_objects);
 
-                                let distance = self.player.position.manhattan_distance(&target_pos);
-                                if distance <= 2 {
-                                    should_harvest = true;
-                                } 
+                                    let distance = self.player.position.manhattan_distance(&target_pos);
+                                    if distance <= HARVEST_RANGE {
+                                        should_harvest = true;
+                                    }
+                                }
                             }
                         } else {
-                            // Wrong letter, clear this word
+                            // Wrong letter, clear this word and break the combo
                             self.stats.add_mistake();
+                            self.combo.break_combo();
+                            self.achievements.record(GameEvent::Mistake);
+                            resource.mistakes += 1;
                             resource.word_start_time = None;
                             resource.current_input.clear();
                             resource.path.clear();
@@ -664,9 +1259,33 @@ impl Game {
                     }
                 }
 
+                if word_xp > 0 {
+                    self.award_xp(word_xp);
+                }
+                if let Some(event) = word_event {
+                    let unlocked = self.achievements.record(event);
+                    self.announce_achievements(unlocked);
+                }
+
+                // In walking mode the finished word is queued and harvested on arrival
+                if let Some(resource) = walk_to {
+                    let harvest = QueuedHarvest {
+                        resource,
+                        performance: word_performance.take(),
+                    };
+                    if !self.walker.queue(harvest) {
+                        self.effects.add_styled_text(
+                            "Queue full!".to_string(),
+                            self.player.position.x as f32,
+                            self.player.position.y as f32 - 1.0,
+                            TextStyle::sinking(Color::Red)
+                        );
+                    }
+                }
+
                 // Handle harvest after the loop
                 if should_harvest {
-                    self.harvest_resource();
+                    self.harvest_resource(word_performance.as_ref());
                     self.player.target = None;
                 }
 
@@ -687,192 +1306,96 @@ impl Game {
         None
     }
 
-    fn render_game_area(&self, f: &mut Frame, game_area: Rect) {
-        let mut lines = Vec::new();
-        
-        // Create empty grid
-        for y in 0..game_area.height {
-            let mut line_spans = Vec::new();
-            for x in 0..game_area.width {
-                let pos = Position::new(x as i32, y as i32);
-                
-                // Add resource counter at top-right if we're at the right position
-                if y == 0 && x >= game_area.width.saturating_sub(30) {
-                    if x == game_area.width.saturating_sub(30) {
-                        let wood_text = format!("Wood: {}", self.player.wood);
-                        let copper_text = format!("Copper: {}", self.player.copper);
-                        line_spans.push(Span::styled(
-                            wood_text,
-                            Style::default().fg(ResourceType::Wood.get_color())
-                        ));
-                        line_spans.push(Span::raw(" | "));
-                        line_spans.push(Span::styled(
-                            copper_text,
-                            Style::default().fg(ResourceType::Copper.get_color())
-                        ));
-                        // Skip the rest of this line
-                        break;
-                    }
-                    continue;
-                }
-                
-                // Add completed items display on the right side
-                if x >= game_area.width.saturating_sub(25) {
-                    let right_area_x = x as usize - (game_area.width.saturating_sub(25) as usize);
-                    
-                    // Show completed items (starting from line 2)
-                    let completed_items = self.crafting.get_completed_items();
-                    for (item_idx, item) in completed_items.iter().enumerate() {
-                        let item_line = 2 + item_idx as u16;
-                        if y == item_line && right_area_x < item.len() {
-                            if let Some(c) = item.chars().nth(right_area_x) {
-                                line_spans.push(Span::styled(
-                                    c.to_string(),
-                                    Style::default().fg(Color::Green)
-                                ));
-                                continue;
-                            }
-                        }
-                    }
-                    
-                    // Fill with spaces if nothing to show
-                    line_spans.push(Span::raw(" "));
-                    continue;
-                }
-                
-                // Get the coastline/water tile first
-                let (coast_char, coast_style) = self.coastline.get_tile(
-                    x as i32, 
-                    y as i32, 
-                    game_area.width as i32,
-                    game_area.height as i32
-                );
-                
-                // Check if player is here
-                let span = if pos == self.player.position {
-                    Span::styled("@", Style::default().fg(Color::Blue))
-                } else {
-                    // Check if this position is part of any resource's ASCII art
-                    let mut found_char = None;
-                    for resource in &self.resources {
-                        let obj = match resource.resource_type {
-                            ResourceType::Wood => self.resource_objects.get("tree"),
-                            ResourceType::Copper => self.resource_objects.get("copper"),
-                        };
-                        
-                        if let Some(obj) = obj {
-                            let rx = resource.position.x as usize;
-                            let ry = resource.position.y as usize;
-                            let chars = obj.render_at(rx, ry);
-                            if let Some((_, _, c)) = chars.iter().find(|(x, y, _)| *x == pos.x as usize && *y == pos.y as usize) {
-                                found_char = Some((*c, Style::default().fg(resource.resource_type.get_color())));
-                                break;
-                            }
-                        }
-                    }
-                    
-                    if let Some((c, style)) = found_char {
-                        Span::styled(c.to_string(), style)
-                    } else {
-                        // Check if we need to render a word above a resource
-                        let mut word_span = None;
-                        for resource in &self.resources {
-                            let rx = resource.position.x as usize;
-                            let ry = resource.position.y as usize;
-                            
-                            // Position the word centered above the resource
-                            if y as usize == ry - 1 {
-                                let word_start = rx.saturating_sub(resource.craft_sentence.len() / 2);
-                                let word_end = word_start + resource.craft_sentence.len();
-                                let x_pos = x as usize;
-                                
-                                // Current word
-                                if x_pos >= word_start && x_pos < word_end {
-                                    let char_idx = x_pos - word_start;
-                                    if let Some(c) = resource.craft_sentence.chars().nth(char_idx) {
-                                        let style = if char_idx < resource.current_input.len() {
-                                            Style::default().fg(Color::Green)
-                                        } else {
-                                            Style::default().fg(Color::White)
-                                        };
-                                        word_span = Some(Span::styled(c.to_string(), style));
-                                    }
-                                }
-                                // Next word (if not on last harvest)
-                                else if resource.harvests_remaining > 1 {
-                                    let next_start = word_end + 1; // One space after current word
-                                    let next_end = next_start + resource.next_craft_sentence.len();
-                                    if x_pos >= next_start && x_pos < next_end {
-                                        let char_idx = x_pos - next_start;
-                                        if let Some(c) = resource.next_craft_sentence.chars().nth(char_idx) {
-                                            word_span = Some(Span::styled(
-                                                c.to_string(),
-                                                Style::default().fg(Color::DarkGray)
-                                            ));
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        
-                        word_span.unwrap_or_else(|| Span::styled(coast_char, coast_style))
-                    }
-                };
-                line_spans.push(span);
-            }
-            lines.push(Line::from(line_spans));
+    // Keep the camera sized to the game area and following the player
+    fn update_camera(&mut self, game_area: Rect) {
+        let tile_map = self.island_manager.get_tile_map();
+        self.camera.resize(game_area.width.saturating_sub(2), game_area.height.saturating_sub(2));
+
+        // After a resize the old scroll offset is meaningless, so re-centre on the player
+        if self.layout.take_resized() {
+            self.camera.center_on(
+                self.player.position.x,
+                self.player.position.y,
+                tile_map.width(),
+                tile_map.height()
+            );
+            return;
         }
-        
-        // First render the game background and objects
-        let game_widget = Paragraph::new(lines.clone())
-            .block(Block::default().borders(Borders::ALL).title("KeyCrafter - Island 1"));
-        f.render_widget(game_widget, game_area);
-
-        // Then render floating texts on top
-        for floating_text in self.floating_texts.get_texts() {
-            let (x, y) = floating_text.get_position();
-            // Only render floating text within the game area bounds (excluding borders)
-            if y > 0 && y < (game_area.height - 1) as usize && 
-               x > 0 && x < (game_area.width - 1) as usize {
-                
-                // Adjust for the border offset
-                let adjusted_y = y - 1;
-                let adjusted_x = x - 1;
-                
-                if adjusted_y < lines.len() && adjusted_x < game_area.width as usize {
-                    // Get what's currently at this position
-                    let current_line = &lines[adjusted_y];
-                    let mut new_line = current_line.spans.clone();
-                    
-                    // Calculate where in the line to insert the text
-                    let text = floating_text.get_text();
-                    let start_x = adjusted_x.min((game_area.width - 2) as usize - text.len());
-                    
-                    // Replace spans at the text position, but only within bounds
-                    for (i, c) in text.chars().enumerate() {
-                        let pos_x = start_x + i;
-                        if pos_x < new_line.len() && pos_x < (game_area.width - 2) as usize {
-                            let color = floating_text.get_color();
-                            new_line[pos_x] = Span::styled(
-                                c.to_string(),
-                                Style::default().fg(color).add_modifier(Modifier::BOLD)
-                            );
-                        }
-                    }
-                    
-                    // Render just this line within the game area
-                    let text_pos = Rect::new(
-                        game_area.x + 1, // Account for border
-                        game_area.y + 1 + adjusted_y as u16, // Account for border and line position
-                        game_area.width - 2, // Account for borders
-                        1,
-                    );
-                    let text_widget = Paragraph::new(Line::from(new_line));
-                    f.render_widget(text_widget, text_pos);
-                }
-            }
+
+        self.camera.follow(
+            self.player.position.x,
+            self.player.position.y,
+            tile_map.width(),
+            tile_map.height()
+        );
+    }
+
+    fn render_game_area(&self, f: &mut Frame, game_area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title(format!(
+            "KeyCrafter [{}] - {} - Lv {} ({}/{} XP) - {}",
+            self.profile.name,
+            self.island_manager.get_current_island().name,
+            self.progression.level(),
+            self.progression.xp(),
+            PlayerLevel::xp_to_next(self.progression.level()),
+            self.clock.label()
+        ));
+        let view = block.inner(game_area);
+        f.render_widget(block, game_area);
+
+        // Where the player is about to walk, for any word being typed or the current walk
+        let path_preview: HashSet<(i32, i32)> = self.resources
+            .iter()
+            .filter(|r| !r.current_input.is_empty())
+            .flat_map(|r| r.path.iter())
+            .chain(self.player.path.iter())
+            .map(|p| (p.x, p.y))
+            .collect();
+
+        let time_ms = self.resource_objects.animation_time();
+        let sprites: Vec<Sprite> = self.resources
+            .iter()
+            .filter_map(|resource| {
+                let object = self.resource_objects.get(resource.resource_type.get_asset_name())?;
+                // Offset each node's animation so they don't all move in step
+                let phase = (resource.position.x * 131 + resource.position.y * 71) as u64;
+                Some(Sprite {
+                    object,
+                    position: resource.position.clone(),
+                    percent_left: resource.harvests_remaining * 100 / resource.max_harvests.max(1),
+                    time_ms: time_ms + phase,
+                    color: resource.resource_type.get_color(),
+                    greyed: !resource.reachable,  // Resources the player can't get to are greyed out
+                })
+            })
+            .collect();
+
+        let labels: Vec<Label> = self.resources.iter().flat_map(|r| self.word_labels(r)).collect();
+
+        f.render_widget(TerrainLayer {
+            camera: &self.camera,
+            tile_map: self.island_manager.get_tile_map(),
+            coastline: &self.coastline,
+        }, view);
+        f.render_widget(PathLayer { camera: &self.camera, tiles: &path_preview }, view);
+        f.render_widget(ObjectLayer { camera: &self.camera, sprites: &sprites }, view);
+        f.render_widget(LabelLayer { camera: &self.camera, labels: &labels }, view);
+        f.render_widget(EntityLayer { camera: &self.camera, player: &self.player.position }, view);
+        f.render_widget(EffectsLayer { camera: &self.camera, effects: &self.effects }, view);
+        f.render_widget(HudLayer {
+            wood: self.player.wood,
+            copper: self.player.copper,
+            completed_items: self.crafting.get_completed_items(),
+        }, view);
+
+        if self.show_minimap {
+            self.render_minimap(f, game_area);
         }
 
+        self.render_combo_meter(f, game_area);
+        let context = quest_context(&self.stats, &self.crafting, &self.island_manager, &self.resources);
+        self.quests.render_tracker(f, game_area, &context);
+
         // Show update notification if available
         if let Some(version_info) = &self.pending_update {
             let message = self.updater.get_update_message(version_info);
@@ -882,12 +1405,13 @@ impl Game {
                 ])
             }).collect();
 
+            // Keep the popup inside the game area on short terminals
             let update_area = Rect::new(
                 game_area.x + (game_area.width / 4),
                 game_area.y + (game_area.height / 4),
                 game_area.width / 2,
                 (lines.len() + 2) as u16,
-            );
+            ).intersection(game_area);
 
             let update_widget = Paragraph::new(lines)
                 .block(Block::default()
@@ -904,8 +1428,8 @@ impl Game {
             let debug_text = format!("Loaded: Wood={}, Copper={}", self.player.wood, self.player.copper);
             let debug_pos = Rect::new(
                 game_area.x + 1,
-                game_area.y + game_area.height - 2,
-                game_area.width - 2,
+                game_area.y + game_area.height.saturating_sub(2),
+                game_area.width.saturating_sub(2),
                 1,
             );
             let debug_widget = Paragraph::new(Line::from(vec![
@@ -915,25 +1439,181 @@ impl Game {
         }
     }
 
-    fn render_crafting_area(&self, f: &mut Frame, area: Rect) {
+    // The current word centred above a resource, followed by a preview of the next
+    // one unless this is the last harvest or fog hides it
+    fn word_labels(&self, resource: &Resource) -> Vec<Label> {
+        let y = resource.position.y - 1;
+        let word_start = resource.position.x - (resource.craft_sentence.len() / 2) as i32;
+        let current = resource.craft_sentence
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let style = if !resource.reachable {
+                    Style::default().fg(Color::DarkGray).add_modifier(Modifier::CROSSED_OUT)
+                } else if i < resource.current_input.len() {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                (c, style)
+            })
+            .collect();
+
+        let mut labels = vec![Label { x: word_start, y, cells: current }];
+        if resource.harvests_remaining > 1 && !self.clock.hides_next_word() {
+            labels.push(Label {
+                x: word_start + resource.craft_sentence.len() as i32 + 1,  // One space after current word
+                y,
+                cells: resource.next_craft_sentence
+                    .chars()
+                    .map(|c| (c, Style::default().fg(Color::DarkGray)))
+                    .collect(),
+            });
+        }
+        labels
+    }
+
+    // Scaled-down overview of the whole island in the top-left corner
+    fn render_minimap(&self, f: &mut Frame, game_area: Rect) {
+        const MINIMAP_WIDTH: u16 = 26;
+        const MINIMAP_HEIGHT: u16 = 9;
+        let tile_map = self.island_manager.get_tile_map();
+
+        // Pointless if the whole island already fits on screen, and no room on tiny terminals
+        let fits_on_screen = tile_map.width() <= self.camera.width() && tile_map.height() <= self.camera.height();
+        if fits_on_screen || game_area.width < MINIMAP_WIDTH * 2 || game_area.height < MINIMAP_HEIGHT * 2 {
+            return;
+        }
+
+        let inner_width = (MINIMAP_WIDTH - 2) as i32;
+        let inner_height = (MINIMAP_HEIGHT - 2) as i32;
+        let to_cell = |world_x: i32, world_y: i32| {
+            (world_x * inner_width / tile_map.width(), world_y * inner_height / tile_map.height())
+        };
+        let player_cell = to_cell(self.player.position.x, self.player.position.y);
+        let resource_cells: Vec<((i32, i32), Color)> = self.resources.iter()
+            .map(|r| (to_cell(r.position.x, r.position.y), r.resource_type.get_color()))
+            .collect();
+
+        let mut lines = Vec::new();
+        for cy in 0..inner_height {
+            let mut spans = Vec::new();
+            for cx in 0..inner_width {
+                // Sample the tile in the middle of the area this cell covers
+                let world_x = (cx * 2 + 1) * tile_map.width() / (inner_width * 2);
+                let world_y = (cy * 2 + 1) * tile_map.height() / (inner_height * 2);
+
+                let (symbol, mut style) = if (cx, cy) == player_cell {
+                    ("@", Style::default().fg(Color::White).add_modifier(Modifier::BOLD))
+                } else if let Some((_, color)) = resource_cells.iter().find(|(cell, _)| *cell == (cx, cy)) {
+                    ("*", Style::default().fg(*color))
+                } else {
+                    match tile_map.get(world_x, world_y) {
+                        TileKind::DeepWater => ("~", Style::default().fg(Color::Blue)),
+                        TileKind::ShallowWater => ("~", Style::default().fg(Color::Cyan)),
+                        TileKind::Sand => (".", Style::default().fg(Color::LightYellow)),
+                        TileKind::Rock => ("^", Style::default().fg(Color::Gray)),
+                        TileKind::ForestFloor => (",", Style::default().fg(Color::Green)),
+                        TileKind::Grass => ("'", Style::default().fg(Color::Green)),
+                        TileKind::Road => (":", Style::default().fg(Color::Yellow)),
+                    }
+                };
+
+                // Highlight the part of the island currently on screen
+                if self.camera.to_screen(world_x, world_y).is_some() {
+                    style = style.bg(Color::DarkGray);
+                }
+                spans.push(Span::styled(symbol, style));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let minimap_area = Rect::new(game_area.x + 1, game_area.y + 1, MINIMAP_WIDTH, MINIMAP_HEIGHT);
+        f.render_widget(Clear, minimap_area);
+        f.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Map [Tab]")),
+            minimap_area
+        );
+    }
+
+    fn render_combo_meter(&self, f: &mut Frame, game_area: Rect) {
+        if game_area.height < 3 || game_area.width < 4 {
+            return;
+        }
+
+        const METER_WIDTH: usize = 10;
+        let filled = (self.combo.meter_fill() * METER_WIDTH as f32).round() as usize;
+        let meter_color = if self.combo.streak() >= 5 { Color::Magenta } else { Color::Cyan };
+
+        let mut spans = vec![
+            Span::styled(
+                format!("Combo x{} ", self.combo.streak()),
+                Style::default().fg(meter_color).add_modifier(Modifier::BOLD)
+            ),
+            Span::styled("#".repeat(filled), Style::default().fg(meter_color)),
+            Span::styled("-".repeat(METER_WIDTH - filled), Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!(" +{:.0}%", self.combo.combo_bonus() * 100.0),
+                Style::default().fg(Color::Gray)
+            ),
+        ];
+        if let Some(wpm) = self.combo.rolling_wpm() {
+            spans.push(Span::styled(format!(" | {:.0} WPM", wpm), Style::default().fg(Color::Gray)));
+        }
+        if self.movement_mode == MovementMode::Walking {
+            let status = match self.walker.queued() {
+                0 => " | Walking [F2]".to_string(),
+                queued => format!(" | Walking [F2], {} queued", queued),
+            };
+            spans.push(Span::styled(status, Style::default().fg(Color::Gray)));
+        }
+
+        let meter_pos = Rect::new(
+            game_area.x + 1,
+            game_area.y + game_area.height.saturating_sub(2),
+            game_area.width.saturating_sub(2),
+            1,
+        );
+        f.render_widget(Paragraph::new(Line::from(spans)), meter_pos);
+    }
+
+    // In compact mode each recipe is squeezed onto its name line plus the sentence,
+    // without descriptions or blank spacer lines
+    fn render_crafting_area(&self, f: &mut Frame, area: Rect, compact: bool) {
         let recipes = self.crafting.get_recipes();
         let mut crafting_text = Vec::new();
 
         // Title
-        crafting_text.push(Line::from(vec![
-            Span::styled("Crafting", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
-        ]));
-        crafting_text.push(Line::from(""));
+        if !compact {
+            crafting_text.push(Line::from(vec![
+                Span::styled("Crafting", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            ]));
+            crafting_text.push(Line::from(""));
+        }
 
         // Display each recipe
         for (idx, recipe) in recipes.iter().enumerate() {
+            if self.crafting.has_workbench && self.crafting.is_level_locked(idx) &&
+               !(recipe.one_time && self.crafting.has_item(&recipe.name)) {
+                crafting_text.push(Line::from(vec![
+                    Span::styled(
+                        format!("{} - unlocks at level {}", recipe.name, recipe.level_requirement),
+                        Style::default().fg(Color::DarkGray)
+                    )
+                ]));
+                if !compact {
+                    crafting_text.push(Line::from(""));
+                }
+            }
             if self.crafting.is_recipe_unlocked(idx) {
                 // Recipe name and description
                 let mut name_spans = vec![
                     Span::styled(&recipe.name, Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
-                    Span::raw(" - "),
-                    Span::raw(&recipe.description)
                 ];
+                if !compact {
+                    name_spans.push(Span::raw(" - "));
+                    name_spans.push(Span::raw(&recipe.description));
+                }
                 
                 // Add upgrade level if this is an upgrade recipe
                 if recipe.name.starts_with("Upgrade") {
@@ -945,15 +1625,24 @@ impl Game {
                         ));
                         name_spans.push(Span::raw(")"));
                     }
+                } else if recipe.name.starts_with("Hire") && recipe.upgrade_count > 0 {
+                    name_spans.push(Span::styled(
+                        format!(" ({} working)", recipe.upgrade_count),
+                        Style::default().fg(Color::Yellow)
+                    ));
                 }
                 
-                crafting_text.push(Line::from(name_spans));
-
                 // Requirements
                 let requirements = self.crafting.get_requirements_text(recipe);
-                crafting_text.push(Line::from(vec![
-                    Span::styled(format!("Requires: {}", requirements), Style::default().fg(Color::Blue))
-                ]));
+                if compact {
+                    name_spans.push(Span::styled(format!(" ({})", requirements), Style::default().fg(Color::Blue)));
+                    crafting_text.push(Line::from(name_spans));
+                } else {
+                    crafting_text.push(Line::from(name_spans));
+                    crafting_text.push(Line::from(vec![
+                        Span::styled(format!("Requires: {}", requirements), Style::default().fg(Color::Blue))
+                    ]));
+                }
 
                 // Crafting progress
                 if !recipe.current_input.is_empty() {
@@ -984,7 +1673,9 @@ impl Game {
                 } else {
                     // Show the sentence with visible space indicators
                     let mut display_spans = Vec::new();
-                    display_spans.push(Span::styled("Type to craft: ", Style::default().fg(Color::Gray)));
+                    if !compact {
+                        display_spans.push(Span::styled("Type to craft: ", Style::default().fg(Color::Gray)));
+                    }
                     
                     for c in recipe.craft_sentence.chars() {
                         let display_char = if c == ' ' {
@@ -999,7 +1690,57 @@ impl Game {
                 }
 
                 // Add a blank line between recipes
-                crafting_text.push(Line::from(""));
+                if !compact {
+                    crafting_text.push(Line::from(""));
+                }
+            }
+        }
+
+        // Voyages to other islands, once a boat has been built
+        if self.crafting.has_item("Boat") {
+            crafting_text.push(Line::from(vec![
+                Span::styled("Voyages", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            ]));
+            for (idx, island) in self.island_manager.get_islands().iter().enumerate() {
+                if idx == self.island_manager.current_index() {
+                    continue;
+                }
+
+                if !self.island_manager.can_sail_to(idx, true, self.progression.level()) {
+                    let reason = if self.progression.level() < island.level_requirement {
+                        format!("reach level {}", island.level_requirement)
+                    } else {
+                        let required = island.requires_visited
+                            .and_then(|req| self.island_manager.get_islands().get(req))
+                            .map(|req| req.name.as_str())
+                            .unwrap_or("?");
+                        format!("visit {} first", required)
+                    };
+                    crafting_text.push(Line::from(vec![
+                        Span::styled(
+                            format!("{} - locked ({})", island.name, reason),
+                            Style::default().fg(Color::DarkGray)
+                        )
+                    ]));
+                    continue;
+                }
+
+                crafting_text.push(Line::from(vec![
+                    Span::styled(&island.name, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+                ]));
+
+                let typed = self.island_manager.get_voyage_input(idx).chars().count();
+                let mut voyage_spans = Vec::new();
+                for (i, c) in island.voyage_sentence.chars().enumerate() {
+                    let style = if i < typed {
+                        Style::default().fg(Color::Green)
+                    } else {
+                        Style::default().fg(Color::Gray)
+                    };
+                    let display_char = if c == ' ' { "·".to_string() } else { c.to_string() };
+                    voyage_spans.push(Span::styled(display_char, style));
+                }
+                crafting_text.push(Line::from(voyage_spans));
             }
         }
 
@@ -1013,13 +1754,43 @@ impl Game {
     }
 
     fn get_next_word(&self, resource_type: ResourceType) -> String {
-        let difficulty = match resource_type {
-            ResourceType::Wood => WordDifficulty::Easy,
-            ResourceType::Copper => WordDifficulty::Medium,
-        };
+        let difficulty = self.word_difficulty(&resource_type);
         self.word_list.get_random_word(difficulty).to_string()
     }
 
+    // Pick a word tier for a resource from the tiers the player's level has unlocked
+    fn word_difficulty(&self, resource_type: &ResourceType) -> WordDifficulty {
+        let tiers = self.progression.word_difficulties(resource_type);
+        let mut rng = rand::thread_rng();
+        let difficulty = tiers[rng.gen_range(0..tiers.len())];
+        if self.clock.harder_words() {
+            difficulty.harder()
+        } else {
+            difficulty
+        }
+    }
+
+    fn award_xp(&mut self, amount: u32) {
+        for reward in self.progression.add_xp(amount) {
+            self.player.wood += reward.wood;
+            self.player.copper += reward.copper;
+            self.crafting.set_player_level(reward.level);
+
+            self.effects.add_text(
+                format!("LEVEL UP! Lv {}", reward.level),
+                self.player.position.x as f32,
+                self.player.position.y as f32 - 3.0,
+                Color::LightMagenta
+            );
+            self.effects.add_text(
+                format!("+{} Wood +{} Copper", reward.wood, reward.copper),
+                self.player.position.x as f32,
+                self.player.position.y as f32 - 2.0,
+                Color::LightYellow
+            );
+        }
+    }
+
     fn replace_word(&mut self, idx: usize) {
         // First get the resource type and generate the new word
         let resource_type = self.resources.get(idx)
@@ -1032,21 +1803,46 @@ impl Game {
             resource.craft_sentence = resource.next_craft_sentence.clone();
             resource.next_craft_sentence = new_next;
             resource.current_input.clear();
+            resource.mistakes = 0;
+        }
+    }
+
+    // A newer build saving over us mid-session turns saving off, said once.
+    // Other failures are logged when they first happen, not on every retry
+    fn auto_save_failed(&mut self, error: &(dyn Error + 'static)) {
+        if let Some(MigrationError::NewerVersion(_)) = error.downcast_ref::<MigrationError>() {
+            self.saving_blocked = true;
+            self.warn(format!("Not saving: {}", error));
+            return;
+        }
+        let message = error.to_string();
+        if self.last_save_error.as_ref() != Some(&message) {
+            self.dirs.log(&format!("Auto-save failed: {}", message));
+            self.last_save_error = Some(message);
         }
     }
 
+    // Shown in red for a while and written to the log
+    fn warn(&mut self, message: String) {
+        self.dirs.log(&message);
+        self.effects.add_styled_text(
+            message,
+            self.player.position.x as f32,
+            self.player.position.y as f32 - 3.0,
+            TextStyle::rising(Color::Red).with_lifetime(Duration::from_secs(8))
+        );
+    }
+
     fn save_game(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.saving_blocked {
+            return Err("saving is off for this session".into());
+        }
+
         // Update session time before saving
         self.stats.update_session_time();
 
-        // Validate that we have reasonable data before saving
-        if self.player.wood > 1000 || self.player.copper > 1000 {
-            // eprintln!("Warning: Unusual resource amounts detected, skipping save");
-            return Ok(());
-        }
-
         let save_data = SaveData {
-            version: 1,
+            version: CURRENT_SAVE_VERSION,
             player_wood: self.player.wood,
             player_copper: self.player.copper,
             completed_items: self.crafting.get_completed_items().to_vec(),
@@ -1062,26 +1858,122 @@ impl Game {
                 .map(|r| r.upgrade_count)
                 .unwrap_or(0),
             stats: self.stats.clone(),
+            visited_islands: self.island_manager.visited_names(),
+            player_level: self.progression.level(),
+            player_xp: self.progression.xp(),
+            boots_upgrade_count: self.crafting.get_recipes()
+                .iter()
+                .find(|r| r.name == "Upgrade Boots")
+                .map(|r| r.upgrade_count)
+                .unwrap_or(0),
+            movement_mode: self.movement_mode,
+            woodcutter_count: self.crafting.get_recipes()
+                .iter()
+                .find(|r| r.name == "Hire Woodcutter")
+                .map(|r| r.upgrade_count)
+                .unwrap_or(0),
+            miner_count: self.crafting.get_recipes()
+                .iter()
+                .find(|r| r.name == "Hire Miner")
+                .map(|r| r.upgrade_count)
+                .unwrap_or(0),
+            achievements: self.achievements.unlocked_ids().to_vec(),
+            quests: Some(self.quests.state()),
+            world: Some(self.world_state()),
             save_timestamp: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or(Duration::ZERO)
                 .as_secs(),
         };
 
-        // Debug output to help track saves
-        // println!("Saving: Wood={}, Copper={}", save_data.player_wood, save_data.player_copper);
-        
+        // Progress that play couldn't explain is flagged, but still saved
+        let warnings = save_integrity::check_progress(&self.last_save, &save_data);
+        if let Some(first) = warnings.first() {
+            let more = if warnings.len() > 1 { format!(" (+{} more)", warnings.len() - 1) } else { String::new() };
+            self.effects.add_styled_text(
+                format!("Unusual progress: {}{}", first, more),
+                self.player.position.x as f32,
+                self.player.position.y as f32 - 3.0,
+                TextStyle::rising(Color::Red).with_lifetime(Duration::from_secs(8))
+            );
+            for warning in &warnings {
+                self.dirs.log(&format!("Unusual progress since last save: {}", warning));
+            }
+        }
+
         self.save_manager.save_game(&save_data)?;
+        self.last_save = save_data;
+        self.last_save_error = None;
         Ok(())
     }
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    // Check for update argument
-    let args: Vec<String> = env::args().collect();
-    if args.len() > 1 && args[1] == "update" {
-        return Updater::self_update();
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match CliOptions::parse(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("keycrafter: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    match options.command {
+        Command::Update => return Updater::self_update(),
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let dirs = AppDirs::resolve(options.portable, options.save_dir);
+    if let Err(e) = dirs.create() {
+        eprintln!("keycrafter: can't create the save, config or log directory: {}", e);
+        std::process::exit(1);
+    }
+
+    let profiles = ProfileManager::new(&dirs.save_dir);
+    let moved_save = match profiles.adopt_old_saves(&dirs) {
+        Ok(moved) => moved,
+        Err(e) => {
+            dirs.log(&format!("Couldn't move the old save into a profile: {}", e));
+            None
+        }
+    };
+
+    if let Command::Profile(command) = options.command {
+        if let Err(e) = run_profile_command(&profiles, command) {
+            eprintln!("keycrafter: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
     }
+    if let Command::Save(command) = options.command {
+        if let Err(e) = run_save_command(&dirs, &profiles, options.profile.as_deref(), options.compress, command) {
+            eprintln!("keycrafter: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // The picker only comes up when there is a choice to make
+    let profile = match options.profile {
+        Some(name) => match profiles.get_or_create(&name) {
+            Ok(profile) => Some(profile),
+            Err(e) => {
+                eprintln!("keycrafter: {}", e);
+                std::process::exit(2);
+            }
+        },
+        None => {
+            let mut all = profiles.list();
+            match all.len() {
+                0 => Some(profiles.create(DEFAULT_PROFILE)?),
+                1 => all.pop(),
+                _ => None,
+            }
+        }
+    };
 
     // Regular game startup
     enable_raw_mode()?;
@@ -1090,8 +1982,20 @@ fn main() -> Result<(), Box<dyn Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    let profile = match profile {
+        Some(profile) => profile,
+        None => match pick_profile(&mut terminal, &profiles) {
+            Ok(Some(profile)) => profile,
+            result => {
+                restore_terminal(&mut terminal)?;
+                return result.map(|_| ());
+            }
+        },
+    };
+    profiles.set_last_used(&profile.name);
+
     // Create game state
-    let mut game = Game::new();
+    let mut game = Game::new(dirs, profile, moved_save);
 
     // Game loop with proper cleanup
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
@@ -1109,6 +2013,10 @@ fn main() -> Result<(), Box<dyn Error>> {
                         // Only process key press events, ignore releases
                         if key.kind == KeyEventKind::Press {
                             match key.code {
+                                // Esc closes whatever is open over the game before it quits
+                                KeyCode::Esc if game.screen_open() => {
+                                    game.handle_key(key);
+                                }
                                 KeyCode::F(10) | KeyCode::Esc => {
                                     // Save before exiting
                                     let _ = game.save_game();
@@ -1128,6 +2036,9 @@ fn main() -> Result<(), Box<dyn Error>> {
                             }
                         }
                     }
+                    Ok(Event::Resize(width, height)) => {
+                        game.layout.handle_resize(width, height);
+                    }
                     Ok(_) => {} // Ignore other events
                     Err(e) => {
                         eprintln!("Event read error: {}", e);
@@ -1142,19 +2053,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     }));
 
     // Always restore terminal, even if there was a panic
-    let cleanup_result = (|| -> Result<(), Box<dyn Error>> {
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
-        Ok(())
-    })();
-
-    // Handle cleanup errors
-    if let Err(cleanup_err) = cleanup_result {
+    if let Err(cleanup_err) = restore_terminal(&mut terminal) {
         eprintln!("Failed to cleanup terminal: {}", cleanup_err);
     }
 
@@ -1168,18 +2067,400 @@ fn main() -> Result<(), Box<dyn Error>> {
     }
 }
 
+fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<(), Box<dyn Error>> {
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+// Run the profile picker until someone is chosen. None if they quit instead
+fn pick_profile(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    profiles: &ProfileManager,
+) -> Result<Option<Profile>, Box<dyn Error>> {
+    let mut picker = ProfilePicker::new(profiles);
+    loop {
+        terminal.draw(|f| picker.render(f))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match picker.handle_key(key, profiles) {
+                Some(PickerAction::Play(profile)) => return Ok(Some(profile)),
+                Some(PickerAction::Quit) => return Ok(None),
+                None => {}
+            }
+        }
+    }
+}
+
+// `keycrafter profile ...`, run without starting the game
+fn run_profile_command(profiles: &ProfileManager, command: ProfileCommand) -> Result<(), String> {
+    match command {
+        ProfileCommand::List => {
+            let last_used = profiles.last_used();
+            for profile in profiles.list() {
+                let marker = if Some(&profile.name) == last_used.as_ref() { " (last played)" } else { "" };
+                println!("{}{}", profile.name, marker);
+            }
+        }
+        ProfileCommand::Create(name) => {
+            let profile = profiles.create(&name)?;
+            println!("Created profile '{}' in {}", profile.name, profile.dir().display());
+        }
+        ProfileCommand::Rename(name, new_name) => {
+            profiles.rename(&name, &new_name)?;
+            println!("Renamed profile '{}' to '{}'", name, new_name);
+        }
+        ProfileCommand::Delete(name) => {
+            if profiles.get(&name).is_none() {
+                return Err(format!("no profile named '{}'", name));
+            }
+            if confirm(&format!("Delete profile '{}' and all its progress?", name)) {
+                profiles.delete(&name)?;
+                println!("Deleted profile '{}'", name);
+            } else {
+                println!("Kept profile '{}'", name);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Ask a yes/no question on the terminal. Anything but yes is no
+fn confirm(question: &str) -> bool {
+    print!("{} [y/N] ", question);
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok() && answer.trim().eq_ignore_ascii_case("y")
+}
+
+// The profile a save command works on: the one asked for, else the one played
+// last, else the only one there is
+fn command_profile(profiles: &ProfileManager, name: Option<&str>) -> Result<Profile, String> {
+    if let Some(name) = name {
+        return profiles.get(name).ok_or_else(|| format!("no profile named '{}'", name));
+    }
+    if let Some(profile) = profiles.last_used().and_then(|name| profiles.get(&name)) {
+        return Ok(profile);
+    }
+    let mut all = profiles.list();
+    match all.len() {
+        0 => Err("there are no profiles yet".to_string()),
+        1 => Ok(all.remove(0)),
+        _ => Err("there are several profiles, pick one with --profile".to_string()),
+    }
+}
+
+fn signed_save_manager(dirs: &AppDirs, profile: &Profile) -> SaveManager {
+    let save_manager = SaveManager::with_path(profile.save_file());
+    match InstallKey::load_or_create(&dirs.key_file()) {
+        Ok(key) => save_manager.with_key(key),
+        Err(_) => save_manager,
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+}
+
+// An archive of a save, signed only if this install signed the save itself
+fn archive_save(profile: &Profile, save_manager: &SaveManager, save: &SaveData, signature: Signature) -> Result<SaveArchive, Box<dyn Error>> {
+    let key = save_manager.key().filter(|_| signature == Signature::Valid);
+    SaveArchive::new(&profile.name, profile.load_settings(), save, key)
+}
+
+// Keep the profile's current save next to it before it is replaced or removed,
+// so nothing is lost by mistake: as an archive, or if it won't load, as a copy
+// of its files in a directory. None if there was nothing to keep
+fn archive_current_save(profile: &Profile, save_manager: &SaveManager, reason: &str) -> Result<Option<PathBuf>, Box<dyn Error>> {
+    if !save_manager.exists() {
+        return Ok(None);
+    }
+    let name = format!("{}-{}", reason, unix_now());
+    match save_manager.load_game_signed() {
+        Ok((save, signature)) => {
+            let archive = archive_save(profile, save_manager, &save, signature)?;
+            let path = profile.dir().join(format!("{}.kcsave.gz", name));
+            fs::write(&path, archive.encode(true)?)?;
+            Ok(Some(path))
+        }
+        Err(_) => {
+            let dir = profile.dir().join(name);
+            fs::create_dir_all(&dir)?;
+            for (path, _, _) in save_manager.check_files() {
+                if let Some(file_name) = path.file_name() {
+                    fs::copy(&path, dir.join(file_name))?;
+                }
+            }
+            Ok(Some(dir))
+        }
+    }
+}
+
+// Where archive_current_save kept a save, and how to get it back
+fn describe_kept(kept: &Path) -> String {
+    if kept.is_dir() {
+        format!("{} (it couldn't be loaded, so its files were copied as they are)", kept.display())
+    } else {
+        format!("{} (bring it back with `keycrafter save import`)", kept.display())
+    }
+}
+
+// `keycrafter save ...`, run without starting the game
+fn run_save_command(
+    dirs: &AppDirs,
+    profiles: &ProfileManager,
+    profile_name: Option<&str>,
+    compress: bool,
+    command: SaveCommand,
+) -> Result<(), Box<dyn Error>> {
+    match command {
+        SaveCommand::Export(file) => {
+            let profile = command_profile(profiles, profile_name)?;
+            let save_manager = signed_save_manager(dirs, &profile);
+            if !save_manager.exists() {
+                return Err(format!("profile '{}' has no save yet", profile.name).into());
+            }
+            let (save, signature) = save_manager.load_game_signed()?;
+            if signature == Signature::Invalid {
+                eprintln!("Warning: the save was changed outside the game, exporting it unsigned");
+            }
+
+            let archive = archive_save(&profile, &save_manager, &save, signature)?;
+            let compress = compress || file.extension().is_some_and(|ext| ext == "gz");
+            fs::write(&file, archive.encode(compress)?)?;
+            println!("Exported profile '{}' to {}", profile.name, file.display());
+        }
+        SaveCommand::Import(file) => {
+            let archive = SaveArchive::decode(&fs::read(&file)?)
+                .map_err(|e| format!("can't read {}: {}", file.display(), e))?;
+            // Into the profile it was exported from, unless told otherwise
+            let profile = profiles.get_or_create(profile_name.unwrap_or(&archive.profile))?;
+            let save_manager = signed_save_manager(dirs, &profile);
+            let (mut save, signature) = archive.save_data(save_manager.key())
+                .map_err(|e| format!("the save in {} can't be used: {}", file.display(), e))?;
+            let doubt = match signature {
+                Signature::Valid => None,
+                Signature::Unchecked => Some("The save in the archive isn't signed, so this install can't vouch for it."),
+                Signature::Invalid => Some("The save in the archive was edited or exported by another install."),
+            };
+            if doubt.is_some_and(|doubt| !confirm(&format!("{} Import it anyway, flagged as edited?", doubt))) {
+                println!("Import cancelled");
+                return Ok(());
+            }
+            if save_manager.exists() {
+                if !confirm(&format!("Profile '{}' already has a save. Replace it?", profile.name)) {
+                    println!("Import cancelled");
+                    return Ok(());
+                }
+                if let Some(kept) = archive_current_save(&profile, &save_manager, "before-import")? {
+                    println!("Previous save kept in {}", describe_kept(&kept));
+                }
+            }
+
+            // Loading picks the newest save, so the import has to be the newest.
+            // Only a save this install signed is signed again, so the game
+            // still flags one it couldn't check or vouch for when it's loaded
+            save.save_timestamp = unix_now();
+            let mut save_manager = if signature != Signature::Valid {
+                dirs.log(&format!("Imported a save that isn't signed by this install from {}", file.display()));
+                SaveManager::with_path(profile.save_file())
+            } else {
+                save_manager
+            };
+            save_manager.save_game(&save)?;
+            profile.save_settings(&archive.settings)?;
+            dirs.log(&format!("Imported profile '{}' from {}", profile.name, file.display()));
+            println!("Imported {} into profile '{}'", file.display(), profile.name);
+        }
+        SaveCommand::Show(Some(file)) => {
+            let bytes = fs::read(&file)?;
+            let key = InstallKey::load_or_create(&dirs.key_file()).ok();
+            match SaveArchive::decode(&bytes) {
+                Ok(archive) => {
+                    println!("Archive of profile '{}'", archive.profile);
+                    println!("  Exported:    {} by KeyCrafter {}", format_age(archive.exported_at), archive.game_version);
+                    println!("  Format:      archive v{}", archive.archive_version);
+                    let (save, signature) = archive.save_data(key.as_ref())
+                        .map_err(|e| format!("the save inside can't be used: {}", e))?;
+                    println!("  Signature:   {}", describe_signature(signature));
+                    print_save_summary(&save);
+                }
+                // Not an archive, so try it as a save file
+                Err(_) => {
+                    let json = String::from_utf8(bytes)?;
+                    let (save, signature) = SaveData::from_json_signed(&json, key.as_ref())
+                        .map_err(|e| format!("{} is not a usable save or archive: {}", file.display(), e))?;
+                    println!("Save file {}", file.display());
+                    println!("  Signature:   {}", describe_signature(signature));
+                    print_save_summary(&save);
+                }
+            }
+        }
+        SaveCommand::Show(None) => {
+            let profile = command_profile(profiles, profile_name)?;
+            let save_manager = signed_save_manager(dirs, &profile);
+            println!("Profile '{}' ({})", profile.name, profile.dir().display());
+
+            let files = save_manager.check_files();
+            if files.is_empty() {
+                println!("  No save yet");
+                return Ok(());
+            }
+            for (path, version, loaded) in &files {
+                let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                let version = version.map_or("unknown format".to_string(), |v| format!("format v{}", v));
+                match loaded {
+                    Ok((_, signature)) => println!("  ok       {} ({}, {})", name, version, describe_signature(*signature)),
+                    Err(e) => println!("  DAMAGED  {} ({}): {}", name, version, e),
+                }
+            }
+            if files.iter().all(|(_, _, loaded)| loaded.is_err()) {
+                return Err("none of the save files can be loaded".into());
+            }
+
+            let (save, _) = save_manager.load_game_signed()?;
+            println!();
+            println!("Save the game will load:");
+            print_save_summary(&save);
+        }
+        SaveCommand::Reset => {
+            let profile = command_profile(profiles, profile_name)?;
+            let save_manager = signed_save_manager(dirs, &profile);
+            if !save_manager.exists() {
+                println!("Profile '{}' has no save to reset", profile.name);
+                return Ok(());
+            }
+            if !confirm(&format!("Start profile '{}' over from scratch?", profile.name)) {
+                println!("Kept the save");
+                return Ok(());
+            }
+
+            match archive_current_save(&profile, &save_manager, "before-reset") {
+                Ok(Some(kept)) => println!("Old save kept in {}", describe_kept(&kept)),
+                Ok(None) => {}
+                Err(e) => {
+                    if !confirm(&format!("The old save can't be archived ({}). Delete it anyway?", e)) {
+                        println!("Kept the save");
+                        return Ok(());
+                    }
+                }
+            }
+            save_manager.delete_save()?;
+            dirs.log(&format!("Reset profile '{}'", profile.name));
+            println!("Profile '{}' will start fresh next time", profile.name);
+        }
+    }
+    Ok(())
+}
+
+fn describe_signature(signature: Signature) -> &'static str {
+    match signature {
+        Signature::Valid => "signed by this install",
+        Signature::Unchecked => "not signed",
+        Signature::Invalid => "SIGNATURE MISMATCH: edited, or from another install",
+    }
+}
+
+// "3 days ago", from a unix timestamp
+fn format_age(timestamp: u64) -> String {
+    let seconds = unix_now().saturating_sub(timestamp);
+    let (amount, unit) = match seconds {
+        0..=59 => return "just now".to_string(),
+        60..=3599 => (seconds / 60, "minute"),
+        3600..=86399 => (seconds / 3600, "hour"),
+        _ => (seconds / 86400, "day"),
+    };
+    format!("{} {}{} ago", amount, unit, if amount == 1 { "" } else { "s" })
+}
+
+fn print_save_summary(save: &SaveData) {
+    let stats = &save.stats;
+    let list_or_none = |items: &[String]| if items.is_empty() { "none".to_string() } else { items.join(", ") };
+    let harvested = |resource_type: ResourceType| stats.resources_harvested.get(&resource_type).copied().unwrap_or(0);
+
+    println!("  Saved:       {}", format_age(save.save_timestamp));
+    println!("  Format:      save v{}", save.version);
+    println!("  Level:       {} ({} XP towards the next)", save.player_level, save.player_xp);
+    println!("  Resources:   {} wood, {} copper", save.player_wood, save.player_copper);
+    println!("  Crafted:     {}", list_or_none(&save.completed_items));
+    println!("  Upgrades:    axe {}, pickaxe {}, boots {}",
+        save.axe_upgrade_count, save.pickaxe_upgrade_count, save.boots_upgrade_count);
+    println!("  Workers:     {} woodcutter(s), {} miner(s)", save.woodcutter_count, save.miner_count);
+    let achieved = ACHIEVEMENTS.iter().filter(|a| save.achievements.iter().any(|id| id == a.id)).count();
+    println!("  Achieved:    {} of {} achievements", achieved, ACHIEVEMENTS.len());
+    match &save.quests {
+        Some(state) => {
+            let done = quests::all().iter().filter(|quest| state.completed.contains(&quest.id)).count();
+            println!("  Quests:      {} of {} complete", done, quests::all().len());
+        }
+        None => println!("  Quests:      not started"),
+    }
+    println!("  Islands:     {}", list_or_none(&save.visited_islands));
+    if let Some(world) = &save.world {
+        let nodes = world.island(&world.current_island).map_or(0, |island| island.nodes.len());
+        println!("  Location:    {} ({} resource nodes)", world.current_island, nodes);
+    }
+    println!("  Movement:    {}", save.movement_mode.label());
+    println!("  Play time:   {}", stats.get_total_play_time_formatted());
+    println!("  Words:       {} completed, {:.1}% accuracy, {:.0} WPM average",
+        stats.words_completed, stats.get_accuracy_percentage(), stats.average_wpm);
+    println!("  Harvested:   {} wood, {} copper", harvested(ResourceType::Wood), harvested(ResourceType::Copper));
+    println!("  Crafts:      {} of {} attempts", stats.successful_crafts, stats.crafting_attempts);
+}
+
 fn ui(f: &mut Frame, game: &mut Game) {
     let size = f.size();
-    
-    // Split screen into game area and crafting area
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(24),  // Game area
-            Constraint::Length(12), // Increased crafting area height
-        ])
-        .split(size);
-    
-    game.render_game_area(f, chunks[0]);
-    game.render_crafting_area(f, chunks[1]);
+
+    // Split screen into game area and crafting area, depending on how much room there is
+    let screen = game.layout.compute(size);
+    if screen.mode == LayoutMode::TooSmall {
+        render_too_small(f, size);
+        return;
+    }
+
+    game.update_camera(screen.game_area);
+    game.render_game_area(f, screen.game_area);
+    game.render_crafting_area(f, screen.crafting_area, screen.mode == LayoutMode::Compact);
+
+    match game.overlay {
+        Some(Overlay::Achievements) => game.achievements.render(f, size),
+        Some(Overlay::QuestLog) => {
+            let context = quest_context(&game.stats, &game.crafting, &game.island_manager, &game.resources);
+            game.quests.render_log(f, size, &context);
+        }
+        None => {}
+    }
+    if let Some(report) = &game.away {
+        report.render(f, size);
+    }
+}
+
+fn render_too_small(f: &mut Frame, size: Rect) {
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Terminal too small", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+        ]),
+        Line::from(format!("{}x{}, need {}x{}", size.width, size.height, MIN_WIDTH, MIN_HEIGHT)),
+        Line::from(vec![
+            Span::styled("Resize or press Esc to quit", Style::default().fg(Color::Gray))
+        ]),
+    ];
+
+    // Vertically centre the message, as far as the terminal allows
+    let top = size.height.saturating_sub(lines.len() as u16) / 2;
+    let area = Rect::new(size.x, size.y + top, size.width, size.height - top);
+    let message = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(message, area);
 }
\ No newline at end of file
@@ -0,0 +1,137 @@
+// Time-based walking. In walking mode finishing a word sends the player off to
+// that resource at a steady pace instead of a step per letter. Words finished
+// while already walking queue up and are harvested one after another.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use crate::combo::WordPerformance;
+use crate::pathfinding::{Grid, Position};
+
+// Movement points per second at base speed, enough for four grass tiles
+const BASE_MOVE_POINTS_PER_SECOND: f32 = 80.0;
+pub const MAX_QUEUED_HARVESTS: usize = 5;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MovementMode {
+    #[default]
+    Stepwise,  // One step along the path per correct letter
+    Walking,   // Completed words set a destination the player walks to over time
+}
+
+impl MovementMode {
+    pub fn toggled(self) -> Self {
+        match self {
+            MovementMode::Stepwise => MovementMode::Walking,
+            MovementMode::Walking => MovementMode::Stepwise,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MovementMode::Stepwise => "Step mode",
+            MovementMode::Walking => "Walking mode",
+        }
+    }
+}
+
+// A finished word waiting for the player to reach its resource
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueuedHarvest {
+    pub resource: Position,  // Top-left of the resource, which identifies it
+    pub performance: Option<WordPerformance>,
+}
+
+pub struct Walker {
+    queue: VecDeque<QueuedHarvest>,
+    current: Option<QueuedHarvest>,  // Harvest the player is walking to right now
+    move_points: f32,
+    last_tick: Instant,
+}
+
+impl Walker {
+    pub fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            current: None,
+            move_points: 0.0,
+            last_tick: Instant::now(),
+        }
+    }
+
+    // Returns false if the queue is already full
+    pub fn queue(&mut self, harvest: QueuedHarvest) -> bool {
+        if self.queue.len() >= MAX_QUEUED_HARVESTS {
+            return false;
+        }
+        self.queue.push_back(harvest);
+        true
+    }
+
+    pub fn is_walking(&self) -> bool {
+        self.current.is_some()
+    }
+
+    pub fn queued(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn next_queued(&mut self) -> Option<QueuedHarvest> {
+        self.queue.pop_front()
+    }
+
+    pub fn begin(&mut self, harvest: QueuedHarvest) {
+        self.current = Some(harvest);
+        self.move_points = 0.0;
+    }
+
+    // The harvest the player just arrived at
+    pub fn finish(&mut self) -> Option<QueuedHarvest> {
+        self.current.take()
+    }
+
+    pub fn current(&self) -> Option<&QueuedHarvest> {
+        self.current.as_ref()
+    }
+
+    pub fn queued_harvests(&self) -> impl Iterator<Item = &QueuedHarvest> {
+        self.queue.iter()
+    }
+
+    // Pick up a walk that was saved part way through
+    pub fn restore(&mut self, current: Option<QueuedHarvest>, queue: Vec<QueuedHarvest>) {
+        self.queue = queue.into_iter().take(MAX_QUEUED_HARVESTS).collect();
+        self.current = current;
+        self.move_points = 0.0;
+        self.last_tick = Instant::now();
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.current = None;
+        self.move_points = 0.0;
+    }
+
+    // Walk along the path for the time since the last tick. Terrain costs apply
+    // just like in step mode. Returns true once the destination is reached
+    pub fn tick(&mut self, position: &mut Position, path: &mut Vec<Position>, grid: &Grid, speed: f32) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_tick).as_secs_f32();
+        self.last_tick = now;
+        if self.current.is_none() {
+            return false;
+        }
+
+        self.move_points += elapsed * BASE_MOVE_POINTS_PER_SECOND * speed;
+        while !path.is_empty() {
+            let cost = grid.step_cost(position, &path[0]).unwrap_or(0) as f32;
+            if cost > self.move_points {
+                break;
+            }
+            self.move_points -= cost;
+            *position = path.remove(0);
+        }
+
+        path.is_empty()
+    }
+}
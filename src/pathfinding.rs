@@ -1,7 +1,22 @@
-use std::collections::{BinaryHeap, HashSet};
+use std::collections::BinaryHeap;
 use std::cmp::Ordering;
+use serde::{Deserialize, Serialize};
+use crate::tile_map::{TileKind, TileMap};
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+// Step costs are scaled so diagonals can cost ~sqrt(2) times a straight step
+// while staying in integers. Each step is also multiplied by the tile's own cost.
+pub const STRAIGHT_STEP: u32 = 10;
+pub const DIAGONAL_STEP: u32 = 14;
+
+// Size of the open area used when no island terrain has been loaded
+const FALLBACK_SIZE: i32 = 80;
+
+const NO_PARENT: u32 = u32::MAX;
+
+const STRAIGHT_DIRECTIONS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const ALL_DIRECTIONS: [(i32, i32); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Position {
     pub x: i32,
     pub y: i32,
@@ -16,29 +31,50 @@ impl Position {
         (self.x - other.x).abs() + (self.y - other.y).abs()
     }
 
-    pub fn neighbors(&self) -> Vec<Position> {
-        vec![
-            Position::new(self.x + 1, self.y),
-            Position::new(self.x - 1, self.y),
-            Position::new(self.x, self.y + 1),
-            Position::new(self.x, self.y - 1),
-        ]
-        .into_iter()
-        .filter(|p| p.x >= 0 && p.x < 80 && p.y >= 0 && p.y < 80)
-        .collect()
+    // Shortest distance when diagonal steps are allowed, in step cost units
+    pub fn octile_distance(&self, other: &Position) -> u32 {
+        let dx = (self.x - other.x).unsigned_abs();
+        let dy = (self.y - other.y).unsigned_abs();
+        STRAIGHT_STEP * dx.max(dy) + (DIAGONAL_STEP - STRAIGHT_STEP) * dx.min(dy)
+    }
+}
+
+// Fixed-size set of tile indices packed 64 to a word
+#[derive(Clone)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new(len: usize) -> Self {
+        Self { words: vec![0; len.div_ceil(64)] }
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = 0);
     }
 }
 
 #[derive(Eq, PartialEq)]
 struct Node {
-    position: Position,
-    f_score: i32,
-    g_score: i32,
+    index: u32,
+    f_score: u32,
+    g_score: u32,
 }
 
 impl Ord for Node {
     fn cmp(&self, other: &Self) -> Ordering {
+        // Lowest f first; on ties prefer the node furthest along (highest g)
         other.f_score.cmp(&self.f_score)
+            .then_with(|| self.g_score.cmp(&other.g_score))
     }
 }
 
@@ -48,82 +84,694 @@ impl PartialOrd for Node {
     }
 }
 
+// Scratch space kept between searches so a keystroke doesn't allocate. Scores
+// are only valid for tiles stamped with the current search generation.
+struct SearchBuffers {
+    open_set: BinaryHeap<Node>,
+    closed: BitSet,
+    g_scores: Vec<u32>,
+    came_from: Vec<u32>,
+    stamps: Vec<u32>,
+    generation: u32,
+}
+
+impl SearchBuffers {
+    fn new(len: usize) -> Self {
+        Self {
+            open_set: BinaryHeap::new(),
+            closed: BitSet::new(len),
+            g_scores: vec![u32::MAX; len],
+            came_from: vec![NO_PARENT; len],
+            stamps: vec![0; len],
+            generation: 0,
+        }
+    }
+
+    fn begin(&mut self) {
+        self.open_set.clear();
+        self.closed.clear();
+        self.generation = self.generation.wrapping_add(1);
+        if self.generation == 0 {
+            // Stamps wrapped around, old ones could look current again
+            self.stamps.iter_mut().for_each(|s| *s = 0);
+            self.generation = 1;
+        }
+    }
+
+    fn g_score(&self, index: usize) -> u32 {
+        if self.stamps[index] == self.generation { self.g_scores[index] } else { u32::MAX }
+    }
+
+    fn parent(&self, index: usize) -> u32 {
+        if self.stamps[index] == self.generation { self.came_from[index] } else { NO_PARENT }
+    }
+
+    fn record(&mut self, index: usize, g_score: u32, parent: u32) {
+        self.stamps[index] = self.generation;
+        self.g_scores[index] = g_score;
+        self.came_from[index] = parent;
+    }
+}
+
+// Dense grid indexed y * width + x. Terrain costs and impassable tiles come from the
+// tile map; the obstacle layer holds resource objects and is only rebuilt by the
+// game when resources change.
 pub struct Grid {
-    obstacles: HashSet<Position>,
+    width: i32,
+    height: i32,
+    costs: Vec<u8>,      // Tile cost multiplier from the terrain, 0 if impassable
+    terrain_blocked: BitSet,
+    obstacles: BitSet,
+    reachable: BitSet,   // Tiles connected to the last flood fill start
+    min_cost: u32,       // Cheapest tile on the map, keeps the heuristic admissible
+    uniform_cost: bool,  // Every walkable tile costs the same, so jump points are exact
+    diagonal: bool,
+    jump_points: bool,
+    search: SearchBuffers,
 }
 
 impl Grid {
     pub fn new() -> Self {
-        Self {
-            obstacles: HashSet::new(),
+        let cost = TileKind::Grass.movement_cost().unwrap_or(1);
+        let size = (FALLBACK_SIZE * FALLBACK_SIZE) as usize;
+        let mut grid = Self {
+            width: 0,
+            height: 0,
+            costs: Vec::new(),
+            terrain_blocked: BitSet::new(0),
+            obstacles: BitSet::new(0),
+            reachable: BitSet::new(0),
+            min_cost: 1,
+            uniform_cost: true,
+            diagonal: false,
+            jump_points: false,
+            search: SearchBuffers::new(0),
+        };
+        grid.load_costs(FALLBACK_SIZE, FALLBACK_SIZE, vec![Some(cost); size]);
+        grid
+    }
+
+    // Load the terrain layer. This also clears the obstacle layer, which
+    // belongs to the previous map
+    pub fn set_tile_map(&mut self, tile_map: &TileMap) {
+        let costs = (0..tile_map.height())
+            .flat_map(|y| (0..tile_map.width()).map(move |x| (x, y)))
+            .map(|(x, y)| tile_map.movement_cost(x, y))
+            .collect();
+        self.load_costs(tile_map.width(), tile_map.height(), costs);
+    }
+
+    fn load_costs(&mut self, width: i32, height: i32, costs: Vec<Option<u32>>) {
+        let len = costs.len();
+        self.width = width;
+        self.height = height;
+        self.terrain_blocked = BitSet::new(len);
+        self.obstacles = BitSet::new(len);
+        self.reachable = BitSet::new(len);
+        self.search = SearchBuffers::new(len);
+        self.costs = Vec::with_capacity(len);
+        for (i, cost) in costs.into_iter().enumerate() {
+            match cost {
+                Some(cost) => self.costs.push(cost.clamp(1, u8::MAX as u32) as u8),
+                None => {
+                    self.costs.push(0);
+                    self.terrain_blocked.insert(i);
+                }
+            }
+        }
+
+        let min_cost = self.costs.iter().filter(|&&c| c > 0).min().copied().unwrap_or(1);
+        self.min_cost = min_cost as u32;
+        self.uniform_cost = self.costs.iter().all(|&c| c == 0 || c == min_cost);
+    }
+
+    // Allow 8-direction movement. Diagonals never cut past blocked corners
+    pub fn set_diagonal(&mut self, diagonal: bool) {
+        self.diagonal = diagonal;
+    }
+
+    // Use jump point search where it gives the same paths as A*: diagonal
+    // movement over uniform-cost terrain. Weighted maps always use plain A*
+    pub fn set_jump_points(&mut self, jump_points: bool) {
+        self.jump_points = jump_points;
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            None
+        } else {
+            Some((y * self.width + x) as usize)
         }
     }
 
+    fn position(&self, index: usize) -> Position {
+        Position::new(index as i32 % self.width, index as i32 / self.width)
+    }
+
+    fn walkable_index(&self, index: usize) -> bool {
+        !self.terrain_blocked.contains(index) && !self.obstacles.contains(index)
+    }
+
+    fn walkable_at(&self, x: i32, y: i32) -> bool {
+        self.index(x, y).is_some_and(|i| self.walkable_index(i))
+    }
+
     pub fn add_obstacle(&mut self, pos: Position) {
-        self.obstacles.insert(pos);
+        // Anything off the grid is already impassable
+        if let Some(i) = self.index(pos.x, pos.y) {
+            self.obstacles.insert(i);
+        }
     }
 
     pub fn clear_obstacles(&mut self) {
         self.obstacles.clear();
     }
 
-    pub fn is_walkable(&self, pos: &Position) -> bool {
-        !self.obstacles.contains(pos)
+    // Flood fill everything the start tile connects to. Diagonals can't cut corners,
+    // so 4-way connectivity gives the same regions as 8-way movement
+    pub fn update_reachable(&mut self, start: &Position) {
+        self.reachable.clear();
+        let start_index = match self.index(start.x, start.y) {
+            Some(i) => i,
+            None => return,
+        };
+
+        let mut stack = vec![start_index];
+        self.reachable.insert(start_index);
+        while let Some(index) = stack.pop() {
+            let pos = self.position(index);
+            for (dx, dy) in STRAIGHT_DIRECTIONS {
+                if let Some(i) = self.index(pos.x + dx, pos.y + dy) {
+                    if self.walkable_index(i) && !self.reachable.contains(i) {
+                        self.reachable.insert(i);
+                        stack.push(i);
+                    }
+                }
+            }
+        }
+    }
+
+    // Whether a tile was connected at the last update_reachable()
+    pub fn is_reachable(&self, pos: &Position) -> bool {
+        self.index(pos.x, pos.y).is_some_and(|i| self.reachable.contains(i))
     }
 
-    pub fn find_path(&self, start: Position, goal: Position) -> Option<Vec<Position>> {
-        let mut open_set = BinaryHeap::new();
-        let mut closed_set = HashSet::new();
-        let mut came_from: std::collections::HashMap<Position, Position> = std::collections::HashMap::new();
-        let mut g_scores = std::collections::HashMap::new();
+    // Whether the goal or any tile within `range` steps of it is reachable
+    pub fn is_reachable_within(&self, goal: &Position, range: i32) -> bool {
+        (-range..=range).any(|dy| (-range..=range).any(|dx| {
+            dx.abs() + dy.abs() <= range && self.is_reachable(&Position::new(goal.x + dx, goal.y + dy))
+        }))
+    }
+
+    // Cost of moving between two adjacent tiles, None if the destination can't be entered
+    pub fn step_cost(&self, from: &Position, to: &Position) -> Option<u32> {
+        if from == to {
+            return Some(0);
+        }
+        let tile_cost = self.index(to.x, to.y)
+            .map(|i| self.costs[i] as u32)
+            .filter(|&c| c > 0)?;
+        let step = if from.x != to.x && from.y != to.y { DIAGONAL_STEP } else { STRAIGHT_STEP };
+        Some(step * tile_cost)
+    }
+
+    fn heuristic(&self, from: &Position, goal: &Position) -> u32 {
+        let distance = if self.diagonal {
+            from.octile_distance(goal)
+        } else {
+            from.manhattan_distance(goal) as u32 * STRAIGHT_STEP
+        };
+        distance * self.min_cost
+    }
 
-        // Initialize start node
-        g_scores.insert(start.clone(), 0);
-        open_set.push(Node {
-            position: start.clone(),
-            f_score: 0,
+    pub fn find_path(&mut self, start: Position, goal: Position) -> Option<Vec<Position>> {
+        let start_index = self.index(start.x, start.y)?;
+        let goal_index = self.index(goal.x, goal.y)?;
+        let use_jump_points = self.jump_points && self.diagonal && self.uniform_cost;
+
+        let mut search = std::mem::replace(&mut self.search, SearchBuffers::new(0));
+        search.begin();
+        search.record(start_index, 0, NO_PARENT);
+        search.open_set.push(Node {
+            index: start_index as u32,
+            f_score: self.heuristic(&start, &goal),
             g_score: 0,
         });
 
-        while let Some(current) = open_set.pop() {
-            if current.position == goal {
-                // Reconstruct path
-                let mut path = vec![goal.clone()];
-                let mut current_pos = goal;
-                while let Some(prev) = came_from.get(&current_pos) {
-                    path.push(prev.clone());
-                    current_pos = prev.clone();
+        let found = if use_jump_points {
+            self.jump_point_search(&mut search, goal_index)
+        } else {
+            self.a_star(&mut search, goal_index)
+        };
+
+        let path = if found {
+            // Walk the parents back from the goal. Jump point parents can be several
+            // tiles apart, always in a straight or diagonal line, so fill in between
+            let mut path = vec![goal.clone()];
+            let mut index = goal_index;
+            while search.parent(index) != NO_PARENT {
+                let parent = search.parent(index) as usize;
+                let from = self.position(index);
+                let to = self.position(parent);
+                let (dx, dy) = ((to.x - from.x).signum(), (to.y - from.y).signum());
+                let mut step = from;
+                while step != to {
+                    step = Position::new(step.x + dx, step.y + dy);
+                    path.push(step.clone());
                 }
-                path.reverse();
-                return Some(path);
+                index = parent;
             }
+            path.reverse();
+            Some(path)
+        } else {
+            None
+        };
+
+        self.search = search;
+        path
+    }
+
+    // Path to the goal, or failing that to the closest reachable tile within `range`
+    // steps of it, preferring tiles nearer the goal and then nearer the start.
+    // Uses the flood fill from update_reachable() to skip hopeless candidates
+    pub fn find_path_near(&mut self, start: Position, goal: Position, range: i32) -> Option<Vec<Position>> {
+        if let Some(path) = self.find_path(start.clone(), goal.clone()) {
+            return Some(path);
+        }
 
-            if !closed_set.insert(current.position.clone()) {
+        let mut candidates = Vec::new();
+        for dy in -range..=range {
+            for dx in -range..=range {
+                let candidate = Position::new(goal.x + dx, goal.y + dy);
+                if candidate != goal && candidate.manhattan_distance(&goal) <= range && self.is_reachable(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        candidates.sort_by_key(|c| (c.manhattan_distance(&goal), c.octile_distance(&start)));
+
+        candidates.into_iter().find_map(|candidate| self.find_path(start.clone(), candidate))
+    }
+
+    // Weighted A*. Works for any terrain costs, with or without diagonals
+    fn a_star(&self, search: &mut SearchBuffers, goal_index: usize) -> bool {
+        let goal = self.position(goal_index);
+        let directions: &[(i32, i32)] = if self.diagonal { &ALL_DIRECTIONS } else { &STRAIGHT_DIRECTIONS };
+
+        while let Some(current) = search.open_set.pop() {
+            let current_index = current.index as usize;
+            if current_index == goal_index {
+                return true;
+            }
+            if search.closed.contains(current_index) {
                 continue;
             }
+            search.closed.insert(current_index);
 
-            for neighbor in current.position.neighbors() {
-                if !self.is_walkable(&neighbor) || closed_set.contains(&neighbor) {
+            let current_pos = self.position(current_index);
+            for &(dx, dy) in directions {
+                let (nx, ny) = (current_pos.x + dx, current_pos.y + dy);
+                let neighbor_index = match self.index(nx, ny) {
+                    Some(i) if self.walkable_index(i) && !search.closed.contains(i) => i,
+                    _ => continue,
+                };
+
+                // Don't squeeze diagonally between two blocked tiles or around corners
+                if dx != 0 && dy != 0 &&
+                   (!self.walkable_at(current_pos.x + dx, current_pos.y) ||
+                    !self.walkable_at(current_pos.x, current_pos.y + dy)) {
                     continue;
                 }
 
-                let tentative_g_score = current.g_score + 1;
-                let neighbor_g_score = g_scores.get(&neighbor).copied().unwrap_or(i32::MAX);
+                // Rough terrain like sand and shallow water costs more to cross, roads less
+                let neighbor = Position::new(nx, ny);
+                let step_cost = self.step_cost(&current_pos, &neighbor).unwrap_or(STRAIGHT_STEP);
+                let tentative_g_score = current.g_score + step_cost;
 
-                if tentative_g_score < neighbor_g_score {
-                    came_from.insert(neighbor.clone(), current.position.clone());
-                    g_scores.insert(neighbor.clone(), tentative_g_score);
-                    let f_score = tentative_g_score + neighbor.manhattan_distance(&goal);
-                    
-                    open_set.push(Node {
-                        position: neighbor,
-                        f_score,
+                if tentative_g_score < search.g_score(neighbor_index) {
+                    search.record(neighbor_index, tentative_g_score, current.index);
+                    search.open_set.push(Node {
+                        index: neighbor_index as u32,
+                        f_score: tentative_g_score + self.heuristic(&neighbor, &goal),
                         g_score: tentative_g_score,
                     });
                 }
             }
         }
 
-        None
+        false
     }
-} 
\ No newline at end of file
+
+    // Jump point search for uniform-cost grids. Instead of queueing every tile it
+    // scans along straight and diagonal lines and only queues tiles where the
+    // path could turn, which skips most of the open ground on big islands
+    fn jump_point_search(&self, search: &mut SearchBuffers, goal_index: usize) -> bool {
+        let goal = self.position(goal_index);
+
+        while let Some(current) = search.open_set.pop() {
+            let current_index = current.index as usize;
+            if current_index == goal_index {
+                return true;
+            }
+            if search.closed.contains(current_index) {
+                continue;
+            }
+            search.closed.insert(current_index);
+
+            let current_pos = self.position(current_index);
+            let parent = search.parent(current_index);
+            for (dx, dy) in self.pruned_directions(&current_pos, parent) {
+                let jump_point = match self.jump(current_pos.x + dx, current_pos.y + dy, dx, dy, &goal) {
+                    Some(p) => p,
+                    None => continue,
+                };
+                let jump_index = match self.index(jump_point.x, jump_point.y) {
+                    Some(i) if !search.closed.contains(i) => i,
+                    _ => continue,
+                };
+
+                let tentative_g_score = current.g_score + current_pos.octile_distance(&jump_point) * self.min_cost;
+                if tentative_g_score < search.g_score(jump_index) {
+                    search.record(jump_index, tentative_g_score, current.index);
+                    search.open_set.push(Node {
+                        index: jump_index as u32,
+                        f_score: tentative_g_score + self.heuristic(&jump_point, &goal),
+                        g_score: tentative_g_score,
+                    });
+                }
+            }
+        }
+
+        false
+    }
+
+    // Directions worth exploring from a tile given the direction we arrived from
+    fn pruned_directions(&self, pos: &Position, parent: u32) -> Vec<(i32, i32)> {
+        let walkable = |dx: i32, dy: i32| self.walkable_at(pos.x + dx, pos.y + dy);
+
+        if parent == NO_PARENT {
+            // The start tile looks everywhere, still without cutting corners
+            return ALL_DIRECTIONS.iter()
+                .copied()
+                .filter(|&(dx, dy)| walkable(dx, dy) && (dx == 0 || dy == 0 || (walkable(dx, 0) && walkable(0, dy))))
+                .collect();
+        }
+
+        let from = self.position(parent as usize);
+        let dx = (pos.x - from.x).signum();
+        let dy = (pos.y - from.y).signum();
+        let mut directions = Vec::with_capacity(5);
+
+        if dx != 0 && dy != 0 {
+            if walkable(0, dy) {
+                directions.push((0, dy));
+            }
+            if walkable(dx, 0) {
+                directions.push((dx, 0));
+            }
+            if walkable(0, dy) && walkable(dx, 0) {
+                directions.push((dx, dy));
+            }
+        } else if dx != 0 {
+            let (up, down) = (walkable(0, -1), walkable(0, 1));
+            if walkable(dx, 0) {
+                directions.push((dx, 0));
+                if up {
+                    directions.push((dx, -1));
+                }
+                if down {
+                    directions.push((dx, 1));
+                }
+            }
+            if up {
+                directions.push((0, -1));
+            }
+            if down {
+                directions.push((0, 1));
+            }
+        } else {
+            let (left, right) = (walkable(-1, 0), walkable(1, 0));
+            if walkable(0, dy) {
+                directions.push((0, dy));
+                if left {
+                    directions.push((-1, dy));
+                }
+                if right {
+                    directions.push((1, dy));
+                }
+            }
+            if left {
+                directions.push((-1, 0));
+            }
+            if right {
+                directions.push((1, 0));
+            }
+        }
+
+        directions
+    }
+
+    // Scan from (x, y) in direction (dx, dy) until reaching the goal, a tile with a
+    // forced neighbour (where a wall ends and the path may need to turn), or a wall
+    fn jump(&self, mut x: i32, mut y: i32, dx: i32, dy: i32, goal: &Position) -> Option<Position> {
+        loop {
+            if !self.walkable_at(x, y) {
+                return None;
+            }
+            if x == goal.x && y == goal.y {
+                return Some(Position::new(x, y));
+            }
+
+            if dx != 0 && dy != 0 {
+                // A diagonal stops wherever one of its straight scans finds something
+                if self.jump(x + dx, y, dx, 0, goal).is_some() || self.jump(x, y + dy, 0, dy, goal).is_some() {
+                    return Some(Position::new(x, y));
+                }
+                // Keep going only if the diagonal step doesn't cut a corner
+                if !self.walkable_at(x + dx, y) || !self.walkable_at(x, y + dy) {
+                    return None;
+                }
+            } else if dx != 0 {
+                if (self.walkable_at(x, y - 1) && !self.walkable_at(x - dx, y - 1)) ||
+                   (self.walkable_at(x, y + 1) && !self.walkable_at(x - dx, y + 1)) {
+                    return Some(Position::new(x, y));
+                }
+            } else if (self.walkable_at(x - 1, y) && !self.walkable_at(x - 1, y - dy)) ||
+                      (self.walkable_at(x + 1, y) && !self.walkable_at(x + 1, y - dy)) {
+                return Some(Position::new(x, y));
+            }
+
+            x += dx;
+            y += dy;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    // Grid from rows of tiles: '.' grass, ':' road, '~' deep water, '^' rock
+    fn grid(rows: &[&str]) -> Grid {
+        let tiles = rows.iter()
+            .flat_map(|row| row.chars())
+            .map(|c| match c {
+                ':' => TileKind::Road,
+                '~' => TileKind::DeepWater,
+                '^' => TileKind::Rock,
+                _ => TileKind::Grass,
+            })
+            .collect();
+        let map = TileMap::new(rows[0].len() as i32, rows.len() as i32, tiles);
+        let mut grid = Grid::new();
+        grid.set_tile_map(&map);
+        grid
+    }
+
+    fn path_cost(grid: &Grid, path: &[Position]) -> u32 {
+        path.windows(2).map(|step| grid.step_cost(&step[0], &step[1]).unwrap()).sum()
+    }
+
+    // Every step is to a neighbouring open tile, and diagonals have both sides open
+    fn assert_walkable_steps(grid: &Grid, path: &[Position]) {
+        for step in path.windows(2) {
+            let (from, to) = (&step[0], &step[1]);
+            assert!((to.x - from.x).abs() <= 1 && (to.y - from.y).abs() <= 1, "{:?} to {:?} skips tiles", from, to);
+            assert!(grid.walkable_at(to.x, to.y), "{:?} is blocked", to);
+            if from.x != to.x && from.y != to.y {
+                assert!(grid.walkable_at(to.x, from.y) && grid.walkable_at(from.x, to.y), "{:?} to {:?} cuts a corner", from, to);
+            }
+        }
+    }
+
+    // Uniform-cost ground with rocks scattered over about a quarter of it, each
+    // with a random start and goal that may well be rock or cut off
+    fn random_grids(seed: u64, count: usize, width: i32, height: i32) -> impl Iterator<Item = (Grid, Position, Position)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count).map(move |_| {
+            let rows: Vec<String> = (0..height)
+                .map(|_| (0..width).map(|_| if rng.gen_ratio(1, 4) { '^' } else { '.' }).collect())
+                .collect();
+            let mut grid = grid(&rows.iter().map(String::as_str).collect::<Vec<_>>());
+            grid.set_diagonal(true);
+            let start = Position::new(rng.gen_range(0..width), rng.gen_range(0..height));
+            let goal = Position::new(rng.gen_range(0..width), rng.gen_range(0..height));
+            (grid, start, goal)
+        })
+    }
+
+    #[test]
+    fn road_detour_beats_shorter_grass_line() {
+        let mut grid = grid(&[
+            ":::::::::",
+            ".........",
+            ".........",
+        ]);
+        let path = grid.find_path(Position::new(0, 2), Position::new(8, 2)).unwrap();
+        // Up to the road and back down is four steps longer, but cheaper
+        assert_eq!(path.len(), 13);
+        assert_eq!(path_cost(&grid, &path), 150);
+        assert!(path_cost(&grid, &path) < 8 * STRAIGHT_STEP * TileKind::Grass.movement_cost().unwrap());
+        assert!(path.contains(&Position::new(4, 0)));
+    }
+
+    #[test]
+    fn diagonal_steps_follow_the_octile_distance() {
+        let mut grid = grid(&["........"; 6]);
+        grid.set_diagonal(true);
+        let (start, goal) = (Position::new(0, 0), Position::new(6, 3));
+        let path = grid.find_path(start.clone(), goal.clone()).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path_cost(&grid, &path), start.octile_distance(&goal) * 2);
+    }
+
+    #[test]
+    fn deep_water_and_rock_are_never_entered() {
+        let rows = [
+            "..~~~~....",
+            "..~..^^^..",
+            "..~.......",
+            "..~~.^.^^.",
+            ".....^....",
+        ];
+        for diagonal in [false, true] {
+            let mut grid = grid(&rows);
+            grid.set_diagonal(diagonal);
+            // Round the water and through the one gap in the rock
+            let path = grid.find_path(Position::new(0, 0), Position::new(9, 0)).unwrap();
+            assert!(path.contains(&Position::new(5, 2)));
+            for pos in &path {
+                let tile = rows[pos.y as usize].as_bytes()[pos.x as usize];
+                assert!(tile != b'~' && tile != b'^', "entered {:?}", pos);
+            }
+
+            // Walled in by water on every side
+            assert!(grid.find_path(Position::new(0, 0), Position::new(3, 1)).is_some());
+            assert!(grid.find_path(Position::new(0, 0), Position::new(2, 0)).is_none());
+        }
+    }
+
+    #[test]
+    fn jump_points_match_a_star_on_uniform_grids() {
+        for (mut grid, start, goal) in random_grids(34, 300, 24, 16) {
+            grid.set_jump_points(false);
+            let a_star = grid.find_path(start.clone(), goal.clone());
+            grid.set_jump_points(true);
+            let jump_points = grid.find_path(start.clone(), goal.clone());
+
+            match (a_star, jump_points) {
+                (Some(a_star), Some(jump_points)) => {
+                    assert_eq!(jump_points.first(), Some(&start));
+                    assert_eq!(jump_points.last(), Some(&goal));
+                    assert_walkable_steps(&grid, &jump_points);
+                    assert_eq!(path_cost(&grid, &jump_points), path_cost(&grid, &a_star), "{:?} to {:?}", start, goal);
+                }
+                (None, None) => {}
+                (a_star, jump_points) => panic!("{:?} to {:?}: A* found {}, JPS found {}",
+                    start, goal, a_star.is_some(), jump_points.is_some()),
+            }
+        }
+    }
+
+    #[test]
+    fn diagonals_never_cut_corners() {
+        for jump_points in [false, true] {
+            // The only way through is squeezing between two rocks
+            let mut squeeze = grid(&[
+                ".^",
+                "^.",
+            ]);
+            squeeze.set_diagonal(true);
+            squeeze.set_jump_points(jump_points);
+            assert!(squeeze.find_path(Position::new(0, 0), Position::new(1, 1)).is_none());
+
+            // Around a single rock the path has to go the long way round its corner
+            let mut corner = grid(&[
+                "...",
+                ".^.",
+                "...",
+            ]);
+            corner.set_diagonal(true);
+            corner.set_jump_points(jump_points);
+            let path = corner.find_path(Position::new(0, 1), Position::new(1, 0)).unwrap();
+            assert_eq!(path, vec![Position::new(0, 1), Position::new(0, 0), Position::new(1, 0)]);
+
+            for (mut grid, start, goal) in random_grids(7, 100, 16, 12) {
+                grid.set_jump_points(jump_points);
+                if let Some(path) = grid.find_path(start, goal) {
+                    assert_walkable_steps(&grid, &path);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn path_near_ends_at_the_closest_reachable_tile() {
+        let mut grid = grid(&["..........."; 9]);
+        grid.set_diagonal(true);
+        let start = Position::new(0, 4);
+        let goal = Position::new(6, 4);
+
+        // A node on the goal itself: stop next to it, on the side facing the start
+        grid.add_obstacle(goal.clone());
+        grid.update_reachable(&start);
+        let path = grid.find_path_near(start.clone(), goal.clone(), 2).unwrap();
+        assert_eq!(path.last(), Some(&Position::new(5, 4)));
+
+        // Ringed by nodes, the nearest open tiles are two steps out
+        for (dx, dy) in ALL_DIRECTIONS {
+            grid.add_obstacle(Position::new(goal.x + dx, goal.y + dy));
+        }
+        grid.update_reachable(&start);
+        let path = grid.find_path_near(start.clone(), goal.clone(), 2).unwrap();
+        assert_eq!(path.last(), Some(&Position::new(4, 4)));
+        assert!(grid.find_path_near(start, goal, 1).is_none());
+    }
+
+    #[test]
+    fn earlier_searches_dont_leak_into_later_ones() {
+        let rows = ["....^....", "....^....", "........."];
+        let mut grid = grid(&rows);
+        grid.set_diagonal(true);
+        let (start, goal) = (Position::new(0, 0), Position::new(8, 0));
+        let open = grid.find_path(start.clone(), goal.clone()).unwrap();
+
+        // Closing the gap leaves the old scores and parents behind, stamped
+        // with an older generation, so they must not be followed
+        grid.add_obstacle(Position::new(4, 2));
+        assert!(grid.find_path(start.clone(), goal.clone()).is_none());
+        grid.clear_obstacles();
+        assert_eq!(grid.find_path(start.clone(), goal.clone()).unwrap(), open);
+
+        // Nor when the generation counter wraps around
+        grid.search.generation = u32::MAX - 1;
+        for _ in 0..4 {
+            assert_eq!(grid.find_path(start.clone(), goal.clone()).unwrap(), open);
+            grid.add_obstacle(Position::new(4, 2));
+            assert!(grid.find_path(start.clone(), goal.clone()).is_none());
+            grid.clear_obstacles();
+        }
+    }
+}
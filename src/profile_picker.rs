@@ -0,0 +1,178 @@
+// Startup screen for choosing who is playing, with new/rename/delete for
+// looking after the profiles without leaving the game.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use crate::profiles::{Profile, ProfileManager};
+
+pub enum PickerAction {
+    Play(Profile),
+    Quit,
+}
+
+enum Prompt {
+    New(String),
+    Rename(String),
+    ConfirmDelete,
+}
+
+pub struct ProfilePicker {
+    profiles: Vec<Profile>,
+    selected: usize,
+    prompt: Option<Prompt>,
+    message: Option<(String, Color)>,  // Result of the last command
+}
+
+impl ProfilePicker {
+    pub fn new(manager: &ProfileManager) -> Self {
+        let profiles = manager.list();
+        let last_used = manager.last_used();
+        let selected = profiles
+            .iter()
+            .position(|profile| Some(&profile.name) == last_used.as_ref())
+            .unwrap_or(0);
+        Self {
+            profiles,
+            selected,
+            prompt: None,
+            message: None,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, manager: &ProfileManager) -> Option<PickerAction> {
+        match (self.prompt.take(), key.code) {
+            (None, _) => return self.handle_browse_key(key),
+            (Some(_), KeyCode::Esc) => {}  // Cancel
+            (Some(Prompt::New(name)), KeyCode::Enter) => {
+                let result = manager.create(&name);
+                self.finish(manager, result.map(|p| (p.name.clone(), format!("Created '{}'", p.name))));
+            }
+            (Some(Prompt::Rename(name)), KeyCode::Enter) => {
+                let old = self.profiles[self.selected].name.clone();
+                let result = manager.rename(&old, &name);
+                self.finish(manager, result.map(|p| (p.name.clone(), format!("Renamed '{}' to '{}'", old, p.name))));
+            }
+            (Some(Prompt::New(mut name)), code) => {
+                edit_name(&mut name, code);
+                self.prompt = Some(Prompt::New(name));
+            }
+            (Some(Prompt::Rename(mut name)), code) => {
+                edit_name(&mut name, code);
+                self.prompt = Some(Prompt::Rename(name));
+            }
+            (Some(Prompt::ConfirmDelete), KeyCode::Char('y') | KeyCode::Char('Y')) => {
+                let name = self.profiles[self.selected].name.clone();
+                let result = manager.delete(&name);
+                self.finish(manager, result.map(|_| (String::new(), format!("Deleted '{}'", name))));
+            }
+            (Some(Prompt::ConfirmDelete), _) => {}  // Anything but yes cancels
+        }
+        None
+    }
+
+    fn handle_browse_key(&mut self, key: KeyEvent) -> Option<PickerAction> {
+        self.message = None;
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.profiles.len().saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                if let Some(profile) = self.profiles.get(self.selected) {
+                    return Some(PickerAction::Play(profile.clone()));
+                }
+            }
+            KeyCode::Char('n') => self.prompt = Some(Prompt::New(String::new())),
+            KeyCode::Char('r') if !self.profiles.is_empty() => {
+                self.prompt = Some(Prompt::Rename(self.profiles[self.selected].name.clone()));
+            }
+            KeyCode::Char('d') if !self.profiles.is_empty() => self.prompt = Some(Prompt::ConfirmDelete),
+            KeyCode::Esc | KeyCode::F(10) => return Some(PickerAction::Quit),
+            _ => {}
+        }
+        None
+    }
+
+    // Reload the list after a command and select the profile it touched
+    fn finish(&mut self, manager: &ProfileManager, result: Result<(String, String), String>) {
+        self.profiles = manager.list();
+        match result {
+            Ok((name, message)) => {
+                if let Some(index) = self.profiles.iter().position(|profile| profile.name == name) {
+                    self.selected = index;
+                }
+                self.message = Some((message, Color::Green));
+            }
+            Err(e) => self.message = Some((e, Color::Red)),
+        }
+        self.selected = self.selected.min(self.profiles.len().saturating_sub(1));
+    }
+
+    pub fn render(&self, f: &mut Frame) {
+        let size = f.size();
+        let mut lines = vec![Line::from("")];
+        for (i, profile) in self.profiles.iter().enumerate() {
+            let style = if i == self.selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let marker = if i == self.selected { "> " } else { "  " };
+            lines.push(Line::from(Span::styled(format!("{}{}", marker, profile.name), style)));
+        }
+        if self.profiles.is_empty() {
+            lines.push(Line::from(Span::styled("No profiles yet", Style::default().fg(Color::Gray))));
+        }
+        lines.push(Line::from(""));
+
+        let prompt = match &self.prompt {
+            Some(Prompt::New(name)) => Some(format!("New profile: {}_", name)),
+            Some(Prompt::Rename(name)) => Some(format!("Rename to: {}_", name)),
+            Some(Prompt::ConfirmDelete) => Some(format!(
+                "Delete '{}' and all its progress? (y/n)",
+                self.profiles[self.selected].name
+            )),
+            None => None,
+        };
+        match (prompt, &self.message) {
+            (Some(prompt), _) => lines.push(Line::from(Span::styled(prompt, Style::default().fg(Color::Cyan)))),
+            (None, Some((message, color))) => lines.push(Line::from(Span::styled(message.clone(), Style::default().fg(*color)))),
+            (None, None) => lines.push(Line::from("")),
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Enter play  n new  r rename  d delete  Esc quit",
+            Style::default().fg(Color::Gray),
+        )));
+
+        // Centre the box, as far as the terminal allows
+        let width = 60.min(size.width);
+        let height = (lines.len() as u16 + 2).min(size.height);
+        let area = Rect::new(
+            size.x + (size.width - width) / 2,
+            size.y + (size.height - height) / 2,
+            width,
+            height,
+        );
+        let picker = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("KeyCrafter - Who's playing?"))
+            .alignment(Alignment::Center);
+        f.render_widget(picker, area);
+    }
+}
+
+fn edit_name(name: &mut String, code: KeyCode) {
+    match code {
+        KeyCode::Char(c) => name.push(c),
+        KeyCode::Backspace => {
+            name.pop();
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,245 @@
+// Named player profiles, so several people can share one install. Each profile
+// is a directory under <save dir>/profiles holding its own save, backups and
+// settings.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::app_dirs::{AppDirs, SAVE_FILE_NAME};
+use crate::save_system::SaveManager;
+
+pub const DEFAULT_PROFILE: &str = "default";
+const MAX_NAME_LEN: usize = 24;
+const SETTINGS_FILE_NAME: &str = "settings.json";
+const LAST_PROFILE_FILE_NAME: &str = "last_profile";
+
+// Preferences that belong to a player rather than to a save
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct ProfileSettings {
+    pub show_minimap: bool,
+}
+
+impl Default for ProfileSettings {
+    fn default() -> Self {
+        Self {
+            show_minimap: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    dir: PathBuf,
+}
+
+impl Profile {
+    pub fn save_file(&self) -> PathBuf {
+        self.dir.join(SAVE_FILE_NAME)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    // Missing or unreadable settings fall back to the defaults
+    pub fn load_settings(&self) -> ProfileSettings {
+        fs::read_to_string(self.dir.join(SETTINGS_FILE_NAME))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save_settings(&self, settings: &ProfileSettings) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(settings)?;
+        fs::write(self.dir.join(SETTINGS_FILE_NAME), json)
+    }
+}
+
+pub struct ProfileManager {
+    root: PathBuf,
+}
+
+impl ProfileManager {
+    pub fn new(save_dir: &Path) -> Self {
+        Self {
+            root: save_dir.join("profiles"),
+        }
+    }
+
+    // All profiles, sorted by name
+    pub fn list(&self) -> Vec<Profile> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+        let mut profiles: Vec<Profile> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                validate_name(&name).ok()?;
+                Some(Profile { name, dir: entry.path() })
+            })
+            .collect();
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        profiles
+    }
+
+    pub fn get(&self, name: &str) -> Option<Profile> {
+        self.list().into_iter().find(|profile| profile.name == name)
+    }
+
+    pub fn create(&self, name: &str) -> Result<Profile, String> {
+        validate_name(name)?;
+        if self.get(name).is_some() {
+            return Err(format!("profile '{}' already exists", name));
+        }
+        let dir = self.root.join(name);
+        fs::create_dir_all(&dir).map_err(|e| format!("can't create profile '{}': {}", name, e))?;
+        Ok(Profile { name: name.to_string(), dir })
+    }
+
+    pub fn get_or_create(&self, name: &str) -> Result<Profile, String> {
+        match self.get(name) {
+            Some(profile) => Ok(profile),
+            None => self.create(name),
+        }
+    }
+
+    pub fn rename(&self, name: &str, new_name: &str) -> Result<Profile, String> {
+        validate_name(new_name)?;
+        let profile = self.get(name).ok_or_else(|| format!("no profile named '{}'", name))?;
+        if self.get(new_name).is_some() {
+            return Err(format!("profile '{}' already exists", new_name));
+        }
+        let dir = self.root.join(new_name);
+        fs::rename(&profile.dir, &dir).map_err(|e| format!("can't rename profile '{}': {}", name, e))?;
+
+        if self.last_used().as_deref() == Some(name) {
+            self.set_last_used(new_name);
+        }
+        Ok(Profile { name: new_name.to_string(), dir })
+    }
+
+    // Removes the profile with its save, backups and settings
+    pub fn delete(&self, name: &str) -> Result<(), String> {
+        let profile = self.get(name).ok_or_else(|| format!("no profile named '{}'", name))?;
+        fs::remove_dir_all(&profile.dir).map_err(|e| format!("can't delete profile '{}': {}", name, e))
+    }
+
+    pub fn last_used(&self) -> Option<String> {
+        let name = fs::read_to_string(self.root.join(LAST_PROFILE_FILE_NAME)).ok()?;
+        Some(name.trim().to_string())
+    }
+
+    pub fn set_last_used(&self, name: &str) {
+        let _ = fs::write(self.root.join(LAST_PROFILE_FILE_NAME), name);
+    }
+
+    // Before profiles there was a single save, in the save directory or, before
+    // that, the directory the game was started from. The first time round it
+    // becomes the default profile. Returns where a save was moved to, if one was
+    pub fn adopt_old_saves(&self, dirs: &AppDirs) -> io::Result<Option<PathBuf>> {
+        if !self.list().is_empty() {
+            return Ok(None);
+        }
+        let dir = self.root.join(DEFAULT_PROFILE);
+        let save_manager = SaveManager::with_path(dir.join(SAVE_FILE_NAME));
+
+        let mut moved = save_manager.adopt_saves_from(&dirs.save_dir)?;
+        if let Some(old_dir) = dirs.legacy_save_dir() {
+            moved = moved || save_manager.adopt_saves_from(&old_dir)?;
+        }
+        Ok(if moved { Some(dir) } else { None })
+    }
+}
+
+// Names become directory names, so keep them to characters every filesystem takes
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("profile name can't be empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("profile name can be at most {} characters", MAX_NAME_LEN));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err("profile names can only use letters, digits, '-' and '_'".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_save_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("keycrafter-test-profiles-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn names(manager: &ProfileManager) -> Vec<String> {
+        manager.list().into_iter().map(|profile| profile.name).collect()
+    }
+
+    #[test]
+    fn create_rename_delete() {
+        let manager = ProfileManager::new(&temp_save_dir("crud"));
+        manager.create("bob").unwrap();
+        manager.create("alice").unwrap();
+        assert!(manager.create("bob").is_err());
+        assert_eq!(names(&manager), vec!["alice", "bob"]);
+
+        manager.set_last_used("bob");
+        let renamed = manager.rename("bob", "robert").unwrap();
+        assert_eq!(renamed.name, "robert");
+        assert_eq!(manager.last_used().as_deref(), Some("robert"));
+        assert!(manager.rename("alice", "robert").is_err());
+        assert!(manager.rename("nobody", "carol").is_err());
+
+        manager.delete("alice").unwrap();
+        assert!(manager.delete("alice").is_err());
+        assert_eq!(names(&manager), vec!["robert"]);
+    }
+
+    #[test]
+    fn names_must_be_plain() {
+        for name in ["", "../escape", "a/b", "has space", ".hidden", "x".repeat(MAX_NAME_LEN + 1).as_str()] {
+            assert!(validate_name(name).is_err(), "{:?}", name);
+        }
+        for name in ["bob", "Team_2", "night-shift"] {
+            assert!(validate_name(name).is_ok(), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn settings_are_per_profile() {
+        let manager = ProfileManager::new(&temp_save_dir("settings"));
+        let alice = manager.create("alice").unwrap();
+        let bob = manager.create("bob").unwrap();
+        alice.save_settings(&ProfileSettings { show_minimap: false }).unwrap();
+        assert!(!alice.load_settings().show_minimap);
+        assert!(bob.load_settings().show_minimap);
+    }
+
+    #[test]
+    fn single_save_becomes_default_profile() {
+        let save_dir = temp_save_dir("adopt");
+        fs::write(save_dir.join(SAVE_FILE_NAME), include_str!("../tests/fixtures/save_v1.json")).unwrap();
+        let dirs = AppDirs::resolve(true, Some(save_dir.clone()));
+        let manager = ProfileManager::new(&save_dir);
+
+        let moved = manager.adopt_old_saves(&dirs).unwrap();
+        assert_eq!(moved, Some(save_dir.join("profiles").join(DEFAULT_PROFILE)));
+        let profile = manager.get(DEFAULT_PROFILE).unwrap();
+        let save = SaveManager::with_path(profile.save_file()).load_game_signed().unwrap().0;
+        assert_eq!(save.player_wood, 21);
+
+        // Only ever the first time
+        assert_eq!(manager.adopt_old_saves(&dirs).unwrap(), None);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::resource_types::ResourceType;
+use crate::word_lists::WordDifficulty;
+
+pub const MAX_LEVEL: u32 = 50;
+
+// XP awarded for each kind of action
+pub const XP_PER_WORD_CHAR: u32 = 2;
+pub const XP_PER_HARVEST: u32 = 5;
+pub const XP_PER_CRAFT: u32 = 40;
+pub const XP_PER_VOYAGE: u32 = 60;
+
+// Reward handed out when a new level is reached
+pub struct LevelReward {
+    pub level: u32,
+    pub wood: u32,
+    pub copper: u32,
+}
+
+pub struct PlayerLevel {
+    level: u32,
+    xp: u32,  // XP earned towards the next level
+}
+
+impl PlayerLevel {
+    pub fn new(level: u32, xp: u32) -> Self {
+        Self {
+            level: level.clamp(1, MAX_LEVEL),
+            xp,
+        }
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    pub fn xp(&self) -> u32 {
+        self.xp
+    }
+
+    // XP needed to go from `level` to `level + 1`. Grows a little faster than linear.
+    pub fn xp_to_next(level: u32) -> u32 {
+        let level = level as f32;
+        (100.0 * level.powf(1.4)).round() as u32
+    }
+
+    pub fn reward_for(level: u32) -> LevelReward {
+        LevelReward {
+            level,
+            wood: level * 5,
+            copper: level * 5,
+        }
+    }
+
+    // Add XP and return a reward for every level gained
+    pub fn add_xp(&mut self, amount: u32) -> Vec<LevelReward> {
+        let mut rewards = Vec::new();
+        if self.level >= MAX_LEVEL {
+            return rewards;
+        }
+
+        self.xp += amount;
+        while self.level < MAX_LEVEL && self.xp >= Self::xp_to_next(self.level) {
+            self.xp -= Self::xp_to_next(self.level);
+            self.level += 1;
+            rewards.push(Self::reward_for(self.level));
+        }
+        if self.level >= MAX_LEVEL {
+            self.xp = 0;
+        }
+        rewards
+    }
+
+    // Harder word tiers unlock as the player levels up
+    pub fn word_difficulties(&self, resource_type: &ResourceType) -> &'static [WordDifficulty] {
+        match resource_type {
+            ResourceType::Wood if self.level >= 6 => &[WordDifficulty::Easy, WordDifficulty::Easy, WordDifficulty::Medium],
+            ResourceType::Wood => &[WordDifficulty::Easy],
+            ResourceType::Copper if self.level >= 4 => &[WordDifficulty::Medium, WordDifficulty::Medium, WordDifficulty::Hard],
+            ResourceType::Copper => &[WordDifficulty::Medium],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crafting::CraftingManager;
+    use crate::islands::IslandManager;
+
+    #[test]
+    fn each_level_costs_more_than_the_last() {
+        assert_eq!(PlayerLevel::xp_to_next(1), 100);
+        assert_eq!(PlayerLevel::xp_to_next(2), 264);
+        let costs: Vec<u32> = (1..=MAX_LEVEL).map(PlayerLevel::xp_to_next).collect();
+        for (level, steps) in (2..).zip(costs.windows(3)) {
+            assert!(steps[1] > steps[0]);
+            assert!(steps[2] - steps[1] >= steps[1] - steps[0], "level {} grows slower than linear", level);
+        }
+    }
+
+    #[test]
+    fn one_big_award_can_gain_several_levels() {
+        let mut player = PlayerLevel::new(1, 0);
+        let rewards = player.add_xp(100 + 264 + 50);
+        assert_eq!(rewards.iter().map(|r| r.level).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!((rewards[1].wood, rewards[1].copper), (15, 15));
+        assert_eq!((player.level(), player.xp()), (3, 50));
+
+        // Just short of the next level gains nothing
+        assert!(player.add_xp(PlayerLevel::xp_to_next(3) - 51).is_empty());
+        assert_eq!(player.add_xp(1).len(), 1);
+    }
+
+    #[test]
+    fn levels_stop_at_the_max() {
+        let mut player = PlayerLevel::new(MAX_LEVEL - 1, 0);
+        assert_eq!(player.add_xp(u32::MAX / 2).len(), 1);
+        assert_eq!((player.level(), player.xp()), (MAX_LEVEL, 0));
+        assert!(player.add_xp(1000).is_empty());
+        assert_eq!(player.xp(), 0);
+    }
+
+    #[test]
+    fn saved_levels_are_clamped_before_gating_content() {
+        assert_eq!(PlayerLevel::new(0, 0).level(), 1);
+        assert_eq!(PlayerLevel::new(9999, 0).level(), MAX_LEVEL);
+
+        let islands = IslandManager::new();
+        let cliffs = islands.index_of("Copper Cliffs").unwrap();
+        let mut crafting = CraftingManager::new();
+        let boat = crafting.get_recipes().iter().position(|r| r.name == "Boat").unwrap();
+        for (saved, unlocked) in [(0, false), (2, false), (3, true), (9999, true)] {
+            let level = PlayerLevel::new(saved, 0).level();
+            crafting.set_player_level(level);
+            assert_eq!(!crafting.is_level_locked(boat), level >= 2, "saved level {}", saved);
+            assert_eq!(islands.can_sail_to(cliffs, true, level), unlocked, "saved level {}", saved);
+        }
+    }
+}
@@ -0,0 +1,455 @@
+// Quests: objectives with rewards, defined in resources/quests.json. A quest
+// starts once the quest it requires is complete, so the file describes chains,
+// starting with the tutorial that walks new players through selecting, typing
+// and crafting. Counted objectives (harvests, words) only count what happens
+// after the quest starts. Like achievements, quests are saved by id.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use serde::{Deserialize, Serialize};
+use crate::crafting::CraftingManager;
+use crate::islands::IslandManager;
+use crate::resource_types::ResourceType;
+use crate::save_system::GameStats;
+
+const QUEST_DATA: &str = include_str!("../resources/quests.json");
+
+#[derive(Deserialize, Debug)]
+pub struct Quest {
+    pub id: String,
+    pub title: String,
+    pub hint: String,
+    #[serde(default)]
+    pub tutorial: bool,  // Skipped by players who had already started before quests existed
+    #[serde(default)]
+    pub requires: Option<String>,  // Id of the quest to finish first
+    pub objective: Objective,
+    #[serde(default)]
+    pub reward: Reward,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+    StartWord,
+    Words {
+        count: u32,
+        #[serde(default)]
+        min_accuracy: f32,  // Percent, over every letter typed since the quest started
+    },
+    Harvest { resource: ResourceType, amount: u32 },
+    Craft { item: String },
+    ReachIsland { island: String },
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct Reward {
+    #[serde(default)]
+    pub wood: u32,
+    #[serde(default)]
+    pub copper: u32,
+    #[serde(default)]
+    pub xp: u32,
+}
+
+impl Reward {
+    fn describe(&self) -> String {
+        let parts: Vec<String> = [(self.wood, "wood"), (self.copper, "copper"), (self.xp, "XP")]
+            .iter()
+            .filter(|(amount, _)| *amount > 0)
+            .map(|(amount, name)| format!("{} {}", amount, name))
+            .collect();
+        if parts.is_empty() { "none".to_string() } else { parts.join(", ") }
+    }
+}
+
+// Every quest, in the order they're listed. The data is built in and checked
+// by the tests, so a parse failure can't reach players
+pub fn all() -> &'static [Quest] {
+    static QUESTS: OnceLock<Vec<Quest>> = OnceLock::new();
+    QUESTS.get_or_init(|| serde_json::from_str(QUEST_DATA).unwrap_or_default())
+}
+
+pub fn find(id: &str) -> Option<&'static Quest> {
+    all().iter().find(|quest| quest.id == id)
+}
+
+// The stats a quest counts from, taken when it starts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct Counters {
+    pub harvested: HashMap<ResourceType, u32>,
+    pub words_completed: u32,
+    pub characters_typed: u32,
+    pub mistakes_made: u32,
+}
+
+impl Counters {
+    fn from_stats(stats: &GameStats) -> Self {
+        Self {
+            harvested: stats.resources_harvested.clone(),
+            words_completed: stats.words_completed,
+            characters_typed: stats.characters_typed,
+            mistakes_made: stats.mistakes_made,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StartedQuest {
+    pub id: String,
+    pub baseline: Counters,
+}
+
+// What the save keeps of the quests
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct QuestState {
+    pub completed: Vec<String>,  // Ids, in the order they were finished
+    pub started: Vec<StartedQuest>,
+}
+
+// What the objectives are checked against
+pub struct QuestContext<'a> {
+    pub stats: &'a GameStats,
+    pub crafting: &'a CraftingManager,
+    pub islands: &'a IslandManager,
+    pub typing: bool,  // Some word has been started
+}
+
+pub struct QuestProgress {
+    pub complete: bool,
+    pub label: String,
+}
+
+impl Quest {
+    fn progress(&self, baseline: &Counters, context: &QuestContext) -> QuestProgress {
+        let stats = context.stats;
+        let done = |complete: bool, label: String| QuestProgress { complete, label };
+        match &self.objective {
+            Objective::StartWord => done(context.typing, "Start typing a word".to_string()),
+            Objective::Words { count, min_accuracy } => {
+                let words = stats.words_completed.saturating_sub(baseline.words_completed).min(*count);
+                let typed = stats.characters_typed.saturating_sub(baseline.characters_typed) as f32;
+                let mistakes = stats.mistakes_made.saturating_sub(baseline.mistakes_made) as f32;
+                let accuracy = if typed > 0.0 { ((typed - mistakes) / typed * 100.0).max(0.0) } else { 100.0 };
+                let accurate = accuracy >= *min_accuracy;
+                let mut label = format!("{}/{} words", words, count);
+                if *min_accuracy > 0.0 && words > 0 {
+                    label.push_str(&format!(", {:.0}% accuracy", accuracy));
+                }
+                done(words >= *count && accurate, label)
+            }
+            Objective::Harvest { resource, amount } => {
+                let now = stats.resources_harvested.get(resource).copied().unwrap_or(0);
+                let before = baseline.harvested.get(resource).copied().unwrap_or(0);
+                let harvested = now.saturating_sub(before).min(*amount);
+                let label = format!("{}/{} {}", harvested, amount, resource.get_display_name().to_lowercase());
+                done(harvested >= *amount, label)
+            }
+            Objective::Craft { item } => done(context.crafting.has_crafted(item), format!("Craft {}", item)),
+            Objective::ReachIsland { island } => {
+                let reached = context.islands.index_of(island).is_some_and(|i| context.islands.has_visited(i));
+                done(reached, format!("Reach {}", island))
+            }
+        }
+    }
+}
+
+pub struct QuestManager {
+    completed: Vec<String>,
+    started: Vec<StartedQuest>,
+}
+
+impl QuestManager {
+    // Saves from before quests existed have no quest state. Anyone who has
+    // played already skips the tutorial
+    pub fn new(saved: Option<&QuestState>, stats: &GameStats) -> Self {
+        match saved {
+            Some(state) => Self {
+                completed: state.completed.clone(),
+                started: state.started.clone(),
+            },
+            None => Self {
+                completed: if stats.words_completed > 0 {
+                    all().iter().filter(|quest| quest.tutorial).map(|quest| quest.id.clone()).collect()
+                } else {
+                    Vec::new()
+                },
+                started: Vec::new(),
+            },
+        }
+    }
+
+    pub fn state(&self) -> QuestState {
+        QuestState {
+            completed: self.completed.clone(),
+            started: self.started.clone(),
+        }
+    }
+
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.completed.iter().any(|completed| completed == id)
+    }
+
+    // Starts any quests that have become available and returns those just finished
+    pub fn update(&mut self, context: &QuestContext) -> Vec<&'static Quest> {
+        self.start_available(context.stats);
+
+        let mut finished = Vec::new();
+        let completed = &mut self.completed;
+        // Quests no longer in the data are dropped
+        self.started.retain(|started| match find(&started.id) {
+            Some(quest) if quest.progress(&started.baseline, context).complete => {
+                completed.push(quest.id.clone());
+                finished.push(quest);
+                false
+            }
+            Some(_) => true,
+            None => false,
+        });
+
+        // The next quest in a chain counts from the moment the last one finished
+        if !finished.is_empty() {
+            self.start_available(context.stats);
+        }
+        finished
+    }
+
+    fn start_available(&mut self, stats: &GameStats) {
+        for quest in all() {
+            let available = quest.requires.as_ref().is_none_or(|id| self.is_completed(id));
+            let started = self.started.iter().any(|started| started.id == quest.id);
+            if available && !started && !self.is_completed(&quest.id) {
+                self.started.push(StartedQuest {
+                    id: quest.id.clone(),
+                    baseline: Counters::from_stats(stats),
+                });
+            }
+        }
+    }
+
+    fn active<'a>(&'a self, context: &'a QuestContext) -> impl Iterator<Item = (&'static Quest, QuestProgress)> + 'a {
+        self.started.iter().filter_map(move |started| {
+            let quest = find(&started.id)?;
+            Some((quest, quest.progress(&started.baseline, context)))
+        })
+    }
+
+    // Small panel in the bottom right corner of the game area with the current
+    // quests. Tutorial quests show their hint as well
+    pub fn render_tracker(&self, f: &mut Frame, game_area: Rect, context: &QuestContext) {
+        const WIDTH: u16 = 38;
+        if game_area.width < WIDTH + 24 || game_area.height < 12 {
+            return;
+        }
+        let max_height = game_area.height / 2;
+
+        let mut lines = Vec::new();
+        for (quest, progress) in self.active(context) {
+            let mut entry = vec![
+                Line::from(Span::styled(quest.title.as_str(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+                Line::from(Span::styled(progress.label, Style::default().fg(Color::Gray))),
+            ];
+            if quest.tutorial {
+                for hint_line in wrap_words(&quest.hint, (WIDTH - 2) as usize) {
+                    entry.push(Line::from(Span::styled(hint_line, Style::default().fg(Color::White))));
+                }
+            }
+            if lines.len() + entry.len() + 2 > max_height as usize {
+                break;
+            }
+            lines.extend(entry);
+        }
+        if lines.is_empty() {
+            return;
+        }
+
+        let height = lines.len() as u16 + 2;
+        // Sits just above the combo meter
+        let area = Rect::new(
+            game_area.x + game_area.width - WIDTH - 1,
+            game_area.y + game_area.height - 2 - height,
+            WIDTH,
+            height,
+        );
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Quests [F4]")),
+            area
+        );
+    }
+
+    // The quest log: current quests in full, then everything finished
+    pub fn render_log(&self, f: &mut Frame, size: Rect, context: &QuestContext) {
+        let width = 72.min(size.width);
+        let done = all().iter().filter(|quest| self.is_completed(&quest.id)).count();
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("{} of {} complete", done, all().len()),
+                Style::default().fg(Color::Gray),
+            )),
+            Line::from(""),
+        ];
+
+        for (quest, progress) in self.active(context) {
+            lines.push(Line::from(vec![
+                Span::styled(quest.title.as_str(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::styled(format!(" - {}", progress.label), Style::default().fg(Color::Gray)),
+            ]));
+            for hint_line in wrap_words(&quest.hint, width.saturating_sub(4) as usize) {
+                lines.push(Line::from(format!("  {}", hint_line)));
+            }
+            lines.push(Line::from(Span::styled(
+                format!("  Reward: {}", quest.reward.describe()),
+                Style::default().fg(Color::LightYellow),
+            )));
+        }
+        if self.started.is_empty() {
+            lines.push(Line::from(Span::styled("No quests right now", Style::default().fg(Color::Gray))));
+        }
+
+        let finished: Vec<&Quest> = all().iter().filter(|quest| self.is_completed(&quest.id)).collect();
+        if !finished.is_empty() {
+            lines.push(Line::from(""));
+            for quest in finished {
+                lines.push(Line::from(Span::styled(format!("[x] {}", quest.title), Style::default().fg(Color::DarkGray))));
+            }
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("F4, Enter or Esc to close", Style::default().fg(Color::Gray))));
+
+        let height = (lines.len() as u16 + 2).min(size.height);
+        let area = Rect::new(
+            size.x + (size.width - width) / 2,
+            size.y + (size.height - height) / 2,
+            width,
+            height,
+        );
+        let log = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("Quest Log"))
+            .alignment(Alignment::Left);
+        f.render_widget(Clear, area);
+        f.render_widget(log, area);
+    }
+}
+
+// Breaks text into lines of at most `width` characters at spaces, so the
+// panels know their height before drawing
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(quests: Vec<&Quest>) -> Vec<&str> {
+        quests.iter().map(|quest| quest.id.as_str()).collect()
+    }
+
+    fn context<'a>(stats: &'a GameStats, crafting: &'a CraftingManager, islands: &'a IslandManager) -> QuestContext<'a> {
+        QuestContext { stats, crafting, islands, typing: false }
+    }
+
+    #[test]
+    fn quest_data_is_valid() {
+        let quests: Vec<Quest> = serde_json::from_str(QUEST_DATA).unwrap();
+        assert!(quests[0].tutorial && quests[0].requires.is_none());
+        for quest in &quests {
+            assert_eq!(quests.iter().filter(|other| other.id == quest.id).count(), 1, "{} is listed twice", quest.id);
+            if let Some(required) = &quest.requires {
+                assert!(quests.iter().any(|other| &other.id == required), "{} requires unknown {}", quest.id, required);
+            }
+        }
+        let crafting = CraftingManager::new();
+        let islands = IslandManager::new();
+        for quest in &quests {
+            match &quest.objective {
+                Objective::Craft { item } => assert!(crafting.get_recipes().iter().any(|r| &r.name == item), "{}", item),
+                Objective::ReachIsland { island } => assert!(islands.index_of(island).is_some(), "{}", island),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn tutorial_leads_the_way() {
+        let (crafting, islands) = (CraftingManager::new(), IslandManager::new());
+        let mut stats = GameStats::default();
+        let mut quests = QuestManager::new(None, &stats);
+        assert!(quests.update(&context(&stats, &crafting, &islands)).is_empty());
+        assert_eq!(quests.state().started.len(), 1);
+
+        let mut typing = context(&stats, &crafting, &islands);
+        typing.typing = true;
+        assert_eq!(ids(quests.update(&typing)), vec!["tutorial_pick_word"]);
+
+        // Harvests only count once the quest asking for them has started
+        stats.words_completed = 1;
+        stats.resources_harvested.insert(ResourceType::Wood, 5);
+        assert_eq!(ids(quests.update(&context(&stats, &crafting, &islands))), vec!["tutorial_finish_word"]);
+        stats.resources_harvested.insert(ResourceType::Wood, 19);
+        assert!(quests.update(&context(&stats, &crafting, &islands)).is_empty());
+        stats.resources_harvested.insert(ResourceType::Wood, 20);
+        assert_eq!(ids(quests.update(&context(&stats, &crafting, &islands))), vec!["tutorial_wood"]);
+    }
+
+    #[test]
+    fn accurate_words_need_few_mistakes() {
+        let (crafting, islands) = (CraftingManager::new(), IslandManager::new());
+        let mut stats = GameStats {
+            words_completed: 50,
+            mistakes_made: 400,  // From before the quest, so they don't count
+            ..GameStats::default()
+        };
+        let state = QuestState {
+            completed: all().iter().filter(|q| q.tutorial).map(|q| q.id.clone()).collect(),
+            started: Vec::new(),
+        };
+        let mut quests = QuestManager::new(Some(&state), &stats);
+        quests.update(&context(&stats, &crafting, &islands));
+
+        stats.words_completed += 30;
+        stats.characters_typed += 150;
+        stats.mistakes_made += 10;
+        assert!(quests.update(&context(&stats, &crafting, &islands)).is_empty());
+
+        // The average comes back up with more clean words
+        stats.words_completed += 10;
+        stats.characters_typed += 100;
+        assert_eq!(ids(quests.update(&context(&stats, &crafting, &islands))), vec!["steady_hands"]);
+    }
+
+    #[test]
+    fn players_from_before_quests_skip_the_tutorial() {
+        let stats = GameStats { words_completed: 12, ..GameStats::default() };
+        let quests = QuestManager::new(None, &stats);
+        assert!(all().iter().filter(|q| q.tutorial).all(|q| quests.is_completed(&q.id)));
+        assert!(!quests.is_completed("copper_rush"));
+    }
+
+    #[test]
+    fn hints_wrap_at_spaces() {
+        assert_eq!(wrap_words("type the first letter", 10), vec!["type the", "first", "letter"]);
+    }
+}
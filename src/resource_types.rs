@@ -30,6 +30,14 @@ impl ResourceType {
         }
     }
 
+    // Name of the art asset drawn for this resource
+    pub fn get_asset_name(&self) -> &'static str {
+        match self {
+            ResourceType::Wood => "tree",
+            ResourceType::Copper => "copper",
+        }
+    }
+
     pub fn get_symbol(&self) -> &'static str {
         match self {
             ResourceType::Wood => "/\\",  // Tree symbol
@@ -0,0 +1,156 @@
+// Portable archives of a profile for `keycrafter save export/import`: the save
+// (in its own versioned format, with its checksum and signature), the profile's
+// settings and where it came from. Archives are JSON, optionally gzipped; import tells the
+// two apart by the gzip magic bytes.
+
+use std::io::{Read, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::profiles::ProfileSettings;
+use crate::save_integrity::{InstallKey, Signature};
+use crate::save_system::SaveData;
+
+const ARCHIVE_FORMAT: &str = "keycrafter-save-archive";
+const ARCHIVE_VERSION: u32 = 1;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SaveArchive {
+    pub format: String,
+    pub archive_version: u32,
+    pub game_version: String,  // KeyCrafter version that wrote the archive
+    pub exported_at: u64,
+    pub profile: String,
+    pub settings: ProfileSettings,
+    save: Value,  // The save file's contents, checksum and signature included
+}
+
+impl SaveArchive {
+    // The key signs the save as it goes in. Leave it out for a save this install
+    // didn't sign itself, so the archive doesn't vouch for it either
+    pub fn new(profile: &str, settings: ProfileSettings, save: &SaveData, key: Option<&InstallKey>) -> Result<Self, Box<dyn std::error::Error>> {
+        let save = serde_json::from_str(&save.to_json_signed(key)?)?;
+        Ok(Self {
+            format: ARCHIVE_FORMAT.to_string(),
+            archive_version: ARCHIVE_VERSION,
+            game_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or(Duration::ZERO)
+                .as_secs(),
+            profile: profile.to_string(),
+            settings,
+            save,
+        })
+    }
+
+    pub fn encode(&self, compress: bool) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        if !compress {
+            return Ok(json.into_bytes());
+        }
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(json.as_bytes())?;
+        Ok(encoder.finish()?)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let json = if bytes.starts_with(&GZIP_MAGIC) {
+            let mut json = String::new();
+            GzDecoder::new(bytes).read_to_string(&mut json)?;
+            json
+        } else {
+            String::from_utf8(bytes.to_vec())?
+        };
+
+        // Check what this is before trying to read it as an archive
+        let value: Value = serde_json::from_str(&json)?;
+        if value.get("format").and_then(|f| f.as_str()) != Some(ARCHIVE_FORMAT) {
+            return Err("not a KeyCrafter save archive".into());
+        }
+        let archive: Self = serde_json::from_value(value)?;
+        if archive.archive_version > ARCHIVE_VERSION {
+            return Err(format!(
+                "archive is from a newer version of KeyCrafter ({}), this build reads up to archive v{}",
+                archive.game_version, ARCHIVE_VERSION
+            ).into());
+        }
+        Ok(archive)
+    }
+
+    // The save inside, checked against its checksum and migrated to the current
+    // format. Only archives this install exported come back Valid
+    pub fn save_data(&self, key: Option<&InstallKey>) -> Result<(SaveData, Signature), Box<dyn std::error::Error>> {
+        SaveData::from_json_signed(&self.save.to_string(), key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(key: Option<&InstallKey>) -> SaveArchive {
+        let save = SaveData {
+            player_wood: 77,
+            ..SaveData::default()
+        };
+        SaveArchive::new("alice", ProfileSettings { show_minimap: false }, &save, key).unwrap()
+    }
+
+    #[test]
+    fn round_trips_plain_and_compressed() {
+        for compress in [false, true] {
+            let bytes = archive(None).encode(compress).unwrap();
+            assert_eq!(bytes.starts_with(&GZIP_MAGIC), compress);
+
+            let decoded = SaveArchive::decode(&bytes).unwrap();
+            assert_eq!(decoded.profile, "alice");
+            assert!(!decoded.settings.show_minimap);
+            assert_eq!(decoded.save_data(None).unwrap().0.player_wood, 77);
+        }
+    }
+
+    // A fresh key of its own for each name
+    fn install_key(name: &str) -> InstallKey {
+        let dir = std::env::temp_dir().join(format!("keycrafter-test-archive-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        InstallKey::load_or_create(&dir.join("install.key")).unwrap()
+    }
+
+    #[test]
+    fn only_untouched_archives_from_this_install_verify() {
+        let key = install_key("ours");
+        let decode = |archive: &SaveArchive| SaveArchive::decode(&archive.encode(false).unwrap()).unwrap();
+        assert_eq!(decode(&archive(Some(&key))).save_data(Some(&key)).unwrap().1, Signature::Valid);
+
+        // Another install's archive, or one exported without a signature
+        assert_eq!(decode(&archive(Some(&install_key("theirs")))).save_data(Some(&key)).unwrap().1, Signature::Invalid);
+        assert_eq!(decode(&archive(None)).save_data(Some(&key)).unwrap().1, Signature::Invalid);
+
+        // Edited with the checksum worked out again, which anyone can do
+        let mut edited = archive(Some(&key));
+        let fields = edited.save.as_object_mut().unwrap();
+        fields.remove("checksum");
+        fields.insert("player_wood".to_string(), Value::from(7777));
+        let (save, signature) = decode(&edited).save_data(Some(&key)).unwrap();
+        assert_eq!(save.player_wood, 7777);
+        assert_eq!(signature, Signature::Invalid);
+    }
+
+    #[test]
+    fn rejects_other_files_and_newer_archives() {
+        let save = include_str!("../tests/fixtures/save_v1.json");
+        assert!(SaveArchive::decode(save.as_bytes()).is_err());
+        assert!(SaveArchive::decode(b"\x1f\x8bnot really gzip").is_err());
+
+        let mut newer = archive(None);
+        newer.archive_version = ARCHIVE_VERSION + 1;
+        let bytes = newer.encode(false).unwrap();
+        assert!(SaveArchive::decode(&bytes).unwrap_err().to_string().contains("newer version"));
+    }
+}
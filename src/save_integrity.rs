@@ -0,0 +1,310 @@
+// Tamper checks for saves. Each install has its own random key, and saves are
+// signed with an HMAC of their contents under it, so editing a save by hand
+// (or copying one in from another install) shows up on the next load. On top
+// of that, progress between two saves is compared against what could have been
+// earned in the time between them. Neither check throws progress away: they
+// raise a warning and log it, and the save goes ahead.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use serde_json::Value;
+use sha2::Sha256;
+use crate::idle;
+use crate::progression::PlayerLevel;
+use crate::quests::{self, Quest};
+use crate::resource_types::ResourceType;
+use crate::save_system::SaveData;
+
+const KEY_LEN: usize = 32;
+
+// Generous limits for what fast, lucky play can earn
+const MAX_HARVESTS_PER_SECOND: u64 = 3;
+const MAX_HARVEST_BONUS: u64 = 3;  // Combo and speed bonuses top out at 2.5x
+const PROGRESS_SLACK: u64 = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Signature {
+    Valid,
+    Unchecked,  // No key to check with, or a save from before saves were signed
+    Invalid,    // Missing or wrong: edited outside the game, or signed by another install
+}
+
+pub struct InstallKey {
+    bytes: [u8; KEY_LEN],
+    signed_marker: Option<PathBuf>,  // Created next to the key once it has signed a save
+}
+
+impl InstallKey {
+    // Read this install's key, making a new one the first time round
+    pub fn load_or_create(path: &Path) -> io::Result<Self> {
+        let signed_marker = Some(path.with_extension("signed"));
+        match fs::read_to_string(path) {
+            Ok(text) => match Self::from_hex(text.trim()) {
+                Some(bytes) => Ok(Self { bytes, signed_marker }),
+                None => Err(io::Error::new(io::ErrorKind::InvalidData, format!("{} is not a valid key", path.display()))),
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                // A new key hasn't signed anything, whatever an old marker says
+                if let Some(marker) = &signed_marker {
+                    let _ = fs::remove_file(marker);
+                }
+                let key = Self { bytes: rand::thread_rng().gen(), signed_marker };
+                write_private(path, &hex(&key.bytes))?;
+                Ok(key)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn from_hex(text: &str) -> Option<[u8; KEY_LEN]> {
+        if text.len() != KEY_LEN * 2 || !text.is_ascii() {
+            return None;
+        }
+        let mut bytes = [0u8; KEY_LEN];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&text[i * 2..i * 2 + 2], 16).ok()?;
+        }
+        Some(bytes)
+    }
+
+    // Once this install has signed a save, an unsigned one can only be an edit
+    pub fn has_signed(&self) -> bool {
+        self.signed_marker.as_ref().is_some_and(|marker| marker.exists())
+    }
+
+    pub fn record_signed(&self) -> io::Result<()> {
+        match &self.signed_marker {
+            Some(marker) if !marker.exists() => fs::write(marker, ""),
+            _ => Ok(()),
+        }
+    }
+
+    fn mac(&self, value: &Value) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.bytes).expect("HMAC takes keys of any length");
+        // Compact JSON with sorted keys, the same form the checksum is taken over
+        mac.update(value.to_string().as_bytes());
+        mac
+    }
+
+    pub fn sign(&self, value: &Value) -> String {
+        hex(&self.mac(value).finalize().into_bytes())
+    }
+
+    pub fn verify(&self, value: &Value, signature: &str) -> bool {
+        let bytes: Option<Vec<u8>> = (0..signature.len() / 2)
+            .map(|i| signature.get(i * 2..i * 2 + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+            .collect();
+        match bytes {
+            Some(bytes) => self.mac(value).verify_slice(&bytes).is_ok(),
+            None => false,
+        }
+    }
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Key files are only readable by their owner where the platform allows it
+fn write_private(path: &Path, contents: &str) -> io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()
+}
+
+// Things about the progress since the last save that play couldn't have
+// produced. Empty when everything adds up
+pub fn check_progress(previous: &SaveData, current: &SaveData) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let elapsed = current.save_timestamp.saturating_sub(previous.save_timestamp);
+
+    // Besides harvesting, resources come from level-up and quest rewards and workers
+    let levels = (previous.player_level + 1..=current.player_level)
+        .map(PlayerLevel::reward_for)
+        .map(|r| (r.wood, r.copper));
+    let quests = newly_completed_quests(previous, current)
+        .map(|quest| (quest.reward.wood, quest.reward.copper));
+    let rewarded = levels
+        .chain(quests)
+        .fold((0u64, 0u64), |(wood, copper), (w, c)| (wood + w as u64, copper + c as u64));
+
+    let resources = [
+        (ResourceType::Wood, previous.player_wood, current.player_wood, current.axe_upgrade_count, current.woodcutter_count, rewarded.0),
+        (ResourceType::Copper, previous.player_copper, current.player_copper, current.pickaxe_upgrade_count, current.miner_count, rewarded.1),
+    ];
+    for (resource_type, before, after, upgrades, workers, rewarded) in resources {
+        let name = resource_type.get_display_name();
+        let harvested = harvested(current, &resource_type) as i64 - harvested(previous, &resource_type) as i64;
+        if harvested < 0 {
+            warnings.push(format!("{} harvest count went down by {}", name, -harvested));
+            continue;
+        }
+        let harvested = harvested as u64;
+        let generated = generated(current, &resource_type) as i64 - generated(previous, &resource_type) as i64;
+        if generated < 0 {
+            warnings.push(format!("{} gathered by workers went down by {}", name, -generated));
+            continue;
+        }
+        let generated = generated as u64;
+
+        let max_harvest = elapsed * MAX_HARVESTS_PER_SECOND * (1 + upgrades as u64) * MAX_HARVEST_BONUS + PROGRESS_SLACK;
+        if harvested > max_harvest {
+            warnings.push(format!("{} {} harvested in {}s", harvested, name, elapsed));
+        }
+        // Offline earnings are capped, so this is generous after a long break
+        let max_generated = idle::produced(&resource_type, workers, elapsed) as u64 + PROGRESS_SLACK;
+        if generated > max_generated {
+            warnings.push(format!("{} {} gathered by {} worker(s) in {}s", generated, name, workers, elapsed));
+        }
+        let gained = (after as u64).saturating_sub(before as u64);
+        let earned = harvested + generated + rewarded;
+        if gained > earned {
+            warnings.push(format!("{} rose by {} but only {} was earned", name, gained, earned));
+        }
+    }
+
+    if current.player_level < previous.player_level {
+        warnings.push(format!("level went down from {} to {}", previous.player_level, current.player_level));
+    }
+    let upgrades = [
+        ("Axe", previous.axe_upgrade_count, current.axe_upgrade_count),
+        ("Pickaxe", previous.pickaxe_upgrade_count, current.pickaxe_upgrade_count),
+        ("Boots", previous.boots_upgrade_count, current.boots_upgrade_count),
+        ("Woodcutter", previous.woodcutter_count, current.woodcutter_count),
+        ("Miner", previous.miner_count, current.miner_count),
+    ];
+    for (tool, before, after) in upgrades {
+        if after < before {
+            warnings.push(format!("{} upgrades went down from {} to {}", tool, before, after));
+        }
+    }
+    warnings
+}
+
+fn newly_completed_quests<'a>(previous: &'a SaveData, current: &'a SaveData) -> impl Iterator<Item = &'static Quest> + 'a {
+    let done_before = |id: &String| previous.quests.as_ref().is_some_and(|state| state.completed.contains(id));
+    current.quests
+        .iter()
+        .flat_map(|state| state.completed.iter())
+        .filter(move |id| !done_before(id))
+        .filter_map(|id| quests::find(id))
+}
+
+fn harvested(save: &SaveData, resource_type: &ResourceType) -> u32 {
+    save.stats.resources_harvested.get(resource_type).copied().unwrap_or(0)
+}
+
+fn generated(save: &SaveData, resource_type: &ResourceType) -> u32 {
+    save.stats.resources_generated.get(resource_type).copied().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::quests::QuestState;
+
+    fn key(byte: u8) -> InstallKey {
+        InstallKey { bytes: [byte; KEY_LEN], signed_marker: None }
+    }
+
+    fn save(wood: u32, harvested_wood: u32, timestamp: u64) -> SaveData {
+        let mut save = SaveData {
+            player_wood: wood,
+            save_timestamp: timestamp,
+            ..SaveData::default()
+        };
+        save.stats.resources_harvested.insert(ResourceType::Wood, harvested_wood);
+        save
+    }
+
+    #[test]
+    fn signatures_only_match_their_key_and_contents() {
+        let value = json!({"player_wood": 10, "player_copper": 3});
+        let signature = key(1).sign(&value);
+        assert!(key(1).verify(&value, &signature));
+        assert!(!key(2).verify(&value, &signature));
+        assert!(!key(1).verify(&json!({"player_wood": 9999, "player_copper": 3}), &signature));
+        assert!(!key(1).verify(&value, "not hex"));
+    }
+
+    #[test]
+    fn key_is_created_once_and_reused() {
+        let dir = std::env::temp_dir().join(format!("keycrafter-test-key-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("install.key");
+
+        let first = InstallKey::load_or_create(&path).unwrap();
+        let again = InstallKey::load_or_create(&path).unwrap();
+        assert_eq!(first.bytes, again.bytes);
+
+        fs::write(&path, "garbage").unwrap();
+        assert!(InstallKey::load_or_create(&path).is_err());
+    }
+
+    #[test]
+    fn normal_play_passes() {
+        let level_up = SaveData { player_level: 2, ..save(100 + 5 * 2, 100, 60) };
+        assert!(check_progress(&save(0, 0, 0), &save(40, 60, 60)).is_empty());
+        assert!(check_progress(&save(0, 0, 0), &level_up).is_empty());
+    }
+
+    #[test]
+    fn unearned_resources_are_flagged() {
+        let warnings = check_progress(&save(0, 0, 0), &save(5000, 10, 30));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Wood rose by 5000"), "{:?}", warnings);
+    }
+
+    #[test]
+    fn workers_account_for_their_resources() {
+        let mut hired = save(0, 0, 0);
+        hired.woodcutter_count = 2;
+        let mut later = save(240, 0, 2 * 3600);
+        later.woodcutter_count = 2;
+        later.stats.add_resource_generated(ResourceType::Wood, 240);
+        assert!(check_progress(&hired, &later).is_empty());
+
+        // Ten times what two woodcutters manage in two hours
+        later.player_wood = 2400;
+        later.stats.resources_generated.insert(ResourceType::Wood, 2400);
+        let warnings = check_progress(&hired, &later);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("gathered by 2 worker(s)"), "{:?}", warnings);
+    }
+
+    #[test]
+    fn quest_rewards_are_earned() {
+        let mut before = save(0, 0, 0);
+        before.quests = Some(QuestState::default());
+        let mut after = save(10, 0, 60);
+        after.player_copper = 10;
+        after.quests = Some(QuestState {
+            completed: vec!["tutorial_workbench".to_string()],
+            started: Vec::new(),
+        });
+        assert!(check_progress(&before, &after).is_empty());
+
+        // The same reward can't be claimed twice
+        let warnings = check_progress(&after, &SaveData { player_wood: 20, save_timestamp: 120, ..after.clone() });
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    }
+
+    #[test]
+    fn impossible_harvest_rates_are_flagged() {
+        let warnings = check_progress(&save(0, 0, 0), &save(5000, 5000, 10));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("5000 Wood harvested in 10s"), "{:?}", warnings);
+    }
+}
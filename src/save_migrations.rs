@@ -0,0 +1,254 @@
+// Save files are upgraded one schema version at a time before they are
+// deserialised, so SaveData only ever has to describe the current layout.
+// To change the format: bump CURRENT_SAVE_VERSION, append a migration from the
+// previous version to MIGRATIONS, and add a fixture of the old format to the tests.
+
+use std::fmt;
+use serde_json::{json, Map, Value};
+
+pub const CURRENT_SAVE_VERSION: u32 = 7;
+
+// MIGRATIONS[i] upgrades a save from version i + 1 to version i + 2
+const MIGRATIONS: [fn(&mut Map<String, Value>); (CURRENT_SAVE_VERSION - 1) as usize] = [
+    v1_to_v2,
+    v2_to_v3,
+    v3_to_v4,
+    v4_to_v5,
+    v5_to_v6,
+    v6_to_v7,
+];
+
+#[derive(Debug, PartialEq)]
+pub enum MigrationError {
+    NewerVersion(u32),  // Written by a newer KeyCrafter than this one
+    Invalid(String),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MigrationError::NewerVersion(version) => write!(
+                f,
+                "save is from a newer version of KeyCrafter (format v{}, this build reads up to v{})",
+                version, CURRENT_SAVE_VERSION
+            ),
+            MigrationError::Invalid(reason) => write!(f, "save file is invalid: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+// Format version of a save, without migrating it. Saves from before versioning default to 1
+pub fn version_of(save: &Value) -> Result<u32, MigrationError> {
+    match save.get("version") {
+        None => Ok(1),
+        Some(version) => version
+            .as_u64()
+            .filter(|v| *v >= 1 && *v <= u32::MAX as u64)
+            .map(|v| v as u32)
+            .ok_or_else(|| MigrationError::Invalid(format!("bad version {}", version))),
+    }
+}
+
+// Upgrade a parsed save to the current version
+pub fn migrate(mut save: Value) -> Result<Value, MigrationError> {
+    let mut version = version_of(&save)?;
+    if version > CURRENT_SAVE_VERSION {
+        return Err(MigrationError::NewerVersion(version));
+    }
+
+    let fields = match save.as_object_mut() {
+        Some(fields) => fields,
+        None => return Err(MigrationError::Invalid("not a JSON object".to_string())),
+    };
+    while version < CURRENT_SAVE_VERSION {
+        MIGRATIONS[(version - 1) as usize](fields);
+        version += 1;
+        fields.insert("version".to_string(), json!(version));
+    }
+    Ok(save)
+}
+
+// v2: islands, levels, boots and movement mode were bolted onto v1 with serde
+// defaults. They are always present from v2 on
+fn v1_to_v2(save: &mut Map<String, Value>) {
+    save.entry("visited_islands").or_insert(json!([]));
+    save.entry("player_level").or_insert(json!(1));
+    save.entry("player_xp").or_insert(json!(0));
+    save.entry("boots_upgrade_count").or_insert(json!(0));
+    save.entry("movement_mode").or_insert(json!("Stepwise"));
+}
+
+// v3: saves are signed with the install key (see save_integrity.rs). The layout
+// is the same, but from v3 on a save without a signature has had it stripped
+fn v2_to_v3(_save: &mut Map<String, Value>) {}
+
+// v4: the world itself is saved (see world_state.rs). Older saves have none, so
+// the game spawns a fresh island for them like it always did
+fn v3_to_v4(save: &mut Map<String, Value>) {
+    save.entry("world").or_insert(Value::Null);
+}
+
+// v5: workers gather resources over time (see idle.rs), counted apart from
+// harvests so the progress checks can tell the two apart
+fn v4_to_v5(save: &mut Map<String, Value>) {
+    save.entry("woodcutter_count").or_insert(json!(0));
+    save.entry("miner_count").or_insert(json!(0));
+    if let Some(stats) = save.get_mut("stats").and_then(|stats| stats.as_object_mut()) {
+        stats.entry("resources_generated").or_insert(json!({}));
+    }
+}
+
+// v6: unlocked achievements (see achievements.rs). Anything an older save has
+// already earned unlocks the next time the game checks
+fn v5_to_v6(save: &mut Map<String, Value>) {
+    save.entry("achievements").or_insert(json!([]));
+}
+
+// v7: quest progress (see quests.rs). Older saves have none, and the game
+// skips the tutorial for them if they've been played
+fn v6_to_v7(save: &mut Map<String, Value>) {
+    save.entry("quests").or_insert(Value::Null);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movement::MovementMode;
+    use crate::resource_types::ResourceType;
+    use crate::save_system::SaveData;
+
+    const V1: &str = include_str!("../tests/fixtures/save_v1.json");
+    const V1_LEVELLED: &str = include_str!("../tests/fixtures/save_v1_levelled.json");
+    const V2: &str = include_str!("../tests/fixtures/save_v2.json");
+    const V3: &str = include_str!("../tests/fixtures/save_v3.json");
+    const V4: &str = include_str!("../tests/fixtures/save_v4.json");
+    const V5: &str = include_str!("../tests/fixtures/save_v5.json");
+    const V6: &str = include_str!("../tests/fixtures/save_v6.json");
+    const V7: &str = include_str!("../tests/fixtures/save_v7.json");
+    const FUTURE: &str = include_str!("../tests/fixtures/save_future.json");
+
+    fn load(fixture: &str) -> Result<SaveData, MigrationError> {
+        let value: Value = serde_json::from_str(fixture).unwrap();
+        let migrated = migrate(value)?;
+        Ok(serde_json::from_value(migrated).unwrap())
+    }
+
+    #[test]
+    fn v1_save_gets_defaults_for_new_fields() {
+        let save = load(V1).unwrap();
+        assert_eq!(save.version, CURRENT_SAVE_VERSION);
+        assert_eq!(save.player_wood, 21);
+        assert_eq!(save.player_copper, 21);
+        assert_eq!(save.completed_items, vec!["Workbench".to_string()]);
+        assert_eq!(save.axe_upgrade_count, 1);
+        assert_eq!(save.stats.words_completed, 132);
+        assert!(save.visited_islands.is_empty());
+        assert_eq!(save.player_level, 1);
+        assert_eq!(save.player_xp, 0);
+        assert_eq!(save.boots_upgrade_count, 0);
+        assert_eq!(save.movement_mode, MovementMode::Stepwise);
+    }
+
+    #[test]
+    fn v1_migration_keeps_fields_that_are_already_there() {
+        // Builds between v1 and versioning wrote some of the newer fields with version 1
+        let save = load(V1_LEVELLED).unwrap();
+        assert_eq!(save.player_level, 4);
+        assert_eq!(save.player_xp, 35);
+        assert_eq!(save.visited_islands, vec!["Starter Grove".to_string(), "Copper Cliffs".to_string()]);
+        assert_eq!(save.boots_upgrade_count, 0);
+    }
+
+    #[test]
+    fn current_version_is_unchanged() {
+        let value: Value = serde_json::from_str(V7).unwrap();
+        assert_eq!(migrate(value.clone()).unwrap(), value);
+
+        let save = load(V7).unwrap();
+        let quests = save.quests.unwrap();
+        assert_eq!(quests.completed.len(), 6);
+        assert_eq!(quests.started[0].id, "steady_hands");
+        assert_eq!(quests.started[0].baseline.words_completed, 120);
+        assert_eq!(save.achievements, vec!["first_workbench".to_string(), "visit_copper_cliffs".to_string()]);
+        assert_eq!(save.boots_upgrade_count, 2);
+        assert_eq!(save.woodcutter_count, 1);
+        assert_eq!(save.stats.resources_generated.get(&ResourceType::Wood), Some(&12));
+        assert_eq!(save.movement_mode, MovementMode::Walking);
+
+        let world = save.world.unwrap();
+        assert_eq!(world.current_island, "Copper Cliffs");
+        assert_eq!(world.island("Copper Cliffs").unwrap().nodes[1].current_input, "bir");
+        assert_eq!(world.player.walk_queue.len(), 1);
+    }
+
+    #[test]
+    fn v2_and_v3_saves_have_no_world() {
+        for fixture in [V2, V3] {
+            let value: Value = serde_json::from_str(fixture).unwrap();
+            assert_eq!(migrate(value).unwrap()["world"], Value::Null);
+        }
+        assert!(load(V3).unwrap().world.is_none());
+    }
+
+    #[test]
+    fn v4_save_has_no_workers() {
+        let value: Value = serde_json::from_str(V4).unwrap();
+        let migrated = migrate(value).unwrap();
+        assert_eq!(migrated["woodcutter_count"], json!(0));
+        assert_eq!(migrated["miner_count"], json!(0));
+        assert_eq!(migrated["stats"]["resources_generated"], json!({}));
+    }
+
+    #[test]
+    fn v5_save_has_no_achievements() {
+        let mut value: Value = serde_json::from_str(V5).unwrap();
+        let migrated = migrate(value.clone()).unwrap();
+        value["version"] = json!(7);
+        value["achievements"] = json!([]);
+        value["quests"] = Value::Null;
+        assert_eq!(migrated, value);
+    }
+
+    #[test]
+    fn v6_save_has_no_quests() {
+        let value: Value = serde_json::from_str(V6).unwrap();
+        assert_eq!(migrate(value).unwrap()["quests"], Value::Null);
+        assert!(load(V6).unwrap().quests.is_none());
+    }
+
+    #[test]
+    fn migrated_save_round_trips() {
+        let save = load(V1).unwrap();
+        let json = serde_json::to_string(&save).unwrap();
+        let again = load(&json).unwrap();
+        assert_eq!(again.player_wood, save.player_wood);
+        assert_eq!(again.version, CURRENT_SAVE_VERSION);
+    }
+
+    #[test]
+    fn newer_version_is_refused() {
+        assert_eq!(load(FUTURE).unwrap_err(), MigrationError::NewerVersion(99));
+    }
+
+    #[test]
+    fn missing_version_counts_as_v1() {
+        let save = load(r#"{"player_wood": 3, "player_copper": 0, "completed_items": [],
+            "has_workbench": false, "axe_upgrade_count": 0, "pickaxe_upgrade_count": 0,
+            "stats": {"words_typed": 0, "characters_typed": 0, "resources_harvested": {},
+            "total_play_time_seconds": 0, "session_start_time": 0, "words_completed": 0,
+            "crafting_attempts": 0, "successful_crafts": 0, "mistakes_made": 0,
+            "fastest_word_time": null, "average_wpm": 0.0}, "save_timestamp": 0}"#).unwrap();
+        assert_eq!(save.version, CURRENT_SAVE_VERSION);
+        assert_eq!(save.player_wood, 3);
+    }
+
+    #[test]
+    fn bad_versions_are_invalid() {
+        for save in [r#"{"version": "two"}"#, r#"{"version": 0}"#, r#"[1, 2]"#] {
+            let value: Value = serde_json::from_str(save).unwrap();
+            assert!(matches!(migrate(value), Err(MigrationError::Invalid(_))), "{}", save);
+        }
+    }
+}
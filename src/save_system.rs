@@ -1,15 +1,32 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use crate::resource_types::ResourceType;
+use crate::movement::MovementMode;
+use crate::save_migrations::{self, MigrationError, CURRENT_SAVE_VERSION};
+use crate::save_integrity::{hex, InstallKey, Signature};
+use crate::quests::QuestState;
+use crate::world_state::WorldState;
+
+// Backups are named keycrafter_save.backup.<unix millis>.json next to the save
+const MAX_BACKUPS: usize = 5;
+// Auto-saves come every 30 seconds, so backups are spaced out to cover a longer stretch of play
+const BACKUP_INTERVAL: Duration = Duration::from_secs(5 * 60);
+// First save format that is always signed
+const SIGNED_SINCE_VERSION: u32 = 3;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct GameStats {
     pub words_typed: u32,
     pub characters_typed: u32,
     pub resources_harvested: HashMap<ResourceType, u32>,
+    pub resources_generated: HashMap<ResourceType, u32>,  // Gathered by workers rather than typed for
     pub total_play_time_seconds: u64,
     pub session_start_time: u64,
     pub words_completed: u32,
@@ -26,6 +43,7 @@ impl Default for GameStats {
             words_typed: 0,
             characters_typed: 0,
             resources_harvested: HashMap::new(),
+            resources_generated: HashMap::new(),
             total_play_time_seconds: 0,
             session_start_time: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
@@ -51,13 +69,23 @@ pub struct SaveData {
     pub axe_upgrade_count: u32,
     pub pickaxe_upgrade_count: u32,
     pub stats: GameStats,
+    pub visited_islands: Vec<String>,
+    pub player_level: u32,
+    pub player_xp: u32,
+    pub boots_upgrade_count: u32,
+    pub movement_mode: MovementMode,
+    pub woodcutter_count: u32,
+    pub miner_count: u32,
+    pub achievements: Vec<String>,  // Ids of unlocked achievements
+    pub quests: Option<QuestState>,  // Missing from saves made before quests, which decides who sees the tutorial
+    pub world: Option<WorldState>,  // Missing from saves made before the world was kept
     pub save_timestamp: u64,
 }
 
 impl Default for SaveData {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: CURRENT_SAVE_VERSION,
             player_wood: 0,
             player_copper: 0,
             completed_items: Vec::new(),
@@ -65,6 +93,16 @@ impl Default for SaveData {
             axe_upgrade_count: 0,
             pickaxe_upgrade_count: 0,
             stats: GameStats::default(),
+            visited_islands: Vec::new(),
+            player_level: 1,
+            player_xp: 0,
+            boots_upgrade_count: 0,
+            movement_mode: MovementMode::default(),
+            woodcutter_count: 0,
+            miner_count: 0,
+            achievements: Vec::new(),
+            quests: None,
+            world: None,
             save_timestamp: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or(Duration::ZERO)
@@ -73,23 +111,155 @@ impl Default for SaveData {
     }
 }
 
+impl SaveData {
+    // Parse a save of any known version, checking its checksum and migrating it
+    // to the current layout
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_json_signed(json, None).map(|(save, _)| save)
+    }
+
+    // As from_json, also checking the signature when there's a key to check it with
+    pub fn from_json_signed(json: &str, key: Option<&InstallKey>) -> Result<(Self, Signature), Box<dyn std::error::Error>> {
+        let mut value = parse_checked(json)?;
+        let stored = value.as_object_mut().and_then(|fields| fields.remove("signature"));
+        let signature = match (key, stored) {
+            (None, _) => Signature::Unchecked,
+            (Some(key), Some(Value::String(stored))) if key.verify(&value, &stored) => Signature::Valid,
+            // Saves from before signing have none to check. The version can be
+            // lowered by hand though, so not once this install has signed a save
+            (Some(key), None) if !key.has_signed() && save_migrations::version_of(&value)? < SIGNED_SINCE_VERSION => {
+                Signature::Unchecked
+            }
+            (Some(_), _) => Signature::Invalid,
+        };
+
+        let migrated = save_migrations::migrate(value)?;
+        Ok((serde_json::from_value(migrated)?, signature))
+    }
+
+    // Pretty JSON with a checksum of its contents, as written to disk, signed
+    // with the install key when there is one. The checksum covers the signature too
+    pub fn to_json_signed(&self, key: Option<&InstallKey>) -> Result<String, Box<dyn std::error::Error>> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(key) = key {
+            let signature = key.sign(&value);
+            if let Some(fields) = value.as_object_mut() {
+                fields.insert("signature".to_string(), Value::String(signature));
+            }
+        }
+        let checksum = checksum_of(&value);
+        if let Some(fields) = value.as_object_mut() {
+            fields.insert("checksum".to_string(), Value::String(checksum));
+        }
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+}
+
+// SHA-256 of the compact JSON of everything but the checksum. Object keys come
+// out sorted, so the field order in the file doesn't matter
+fn checksum_of(value: &Value) -> String {
+    hex(&Sha256::digest(value.to_string().as_bytes()))
+}
+
+// Parse a save file and strip its checksum after checking it. Saves from before
+// checksums were added have none and are taken as they are
+fn parse_checked(json: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut value: Value = serde_json::from_str(json)?;
+    let stored = value.as_object_mut().and_then(|fields| fields.remove("checksum"));
+    match stored {
+        None => Ok(value),
+        Some(Value::String(stored)) if stored == checksum_of(&value) => Ok(value),
+        Some(_) => Err("save file checksum doesn't match, it may be damaged".into()),
+    }
+}
+
+// Write to a temporary file, flush it to disk and only then move it over the
+// old file, so a crash mid-save leaves either the old or the new save intact
+fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let temp_path = path.with_extension("json.tmp");
+    {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+    }
+
+    // Verify the save was written correctly before it replaces anything
+    if fs::read_to_string(&temp_path)? != contents {
+        let _ = fs::remove_file(&temp_path);
+        return Err(io::Error::other("Save file verification failed"));
+    }
+    fs::rename(&temp_path, path)?;
+
+    // Make the rename itself durable. Windows can't open directories like this
+    #[cfg(unix)]
+    if let Ok(dir) = File::open(parent_dir(path)) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+// Rename, or copy and remove when the two paths are on different filesystems
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)
+}
+
+fn parent_dir(path: &Path) -> PathBuf {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+// "keycrafter_save.json: save file is invalid: ...", for the first file that wouldn't load
+fn describe_failure(path: &Path, error: &dyn std::error::Error) -> String {
+    let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    format!("{}: {}", name, error)
+}
+
+fn unix_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_millis()
+}
+
+// A save file's path, format version, and either what it holds or why it can't be loaded
+pub type FileCheck = (PathBuf, Option<u32>, Result<(SaveData, Signature), String>);
+
 pub struct SaveManager {
-    save_file_path: String,
-    backup_file_path: String,
+    save_file_path: PathBuf,
     auto_save_interval: Duration,
     last_save_time: SystemTime,
+    max_backups: usize,
+    backup_interval: Duration,  // Minimum time between two backups
+    key: Option<InstallKey>,  // Saves are signed when there is one
 }
 
 impl SaveManager {
-    pub fn new() -> Self {
+    pub fn with_path(save_file_path: PathBuf) -> Self {
         Self {
-            save_file_path: "keycrafter_save.json".to_string(),
-            backup_file_path: "keycrafter_save.backup.json".to_string(),
+            save_file_path,
             auto_save_interval: Duration::from_secs(30), // Auto-save every 30 seconds
             last_save_time: SystemTime::now(),
+            max_backups: MAX_BACKUPS,
+            backup_interval: BACKUP_INTERVAL,
+            key: None,
         }
     }
 
+    pub fn with_key(mut self, key: InstallKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn key(&self) -> Option<&InstallKey> {
+        self.key.as_ref()
+    }
+
     pub fn should_auto_save(&self) -> bool {
         SystemTime::now()
             .duration_since(self.last_save_time)
@@ -98,74 +268,199 @@ impl SaveManager {
     }
 
     pub fn save_game(&mut self, save_data: &SaveData) -> Result<(), Box<dyn std::error::Error>> {
-        let json = serde_json::to_string_pretty(save_data)?;
-
-        // First, try to backup the existing save if it exists
-        if Path::new(&self.save_file_path).exists() {
-            if let Ok(existing_save) = fs::read_to_string(&self.save_file_path) {
-                if let Ok(existing_data) = serde_json::from_str::<SaveData>(&existing_save) {
-                    // Only backup if the existing save has more resources
-                    if existing_data.player_wood > save_data.player_wood || 
-                       existing_data.player_copper > save_data.player_copper {
-                        fs::write(&self.backup_file_path, existing_save)?;
-                        // println!("Created backup of save with more resources");
-                    }
-                }
+        // A failed save waits for the next interval too, rather than retrying every tick
+        self.last_save_time = SystemTime::now();
+        let json = save_data.to_json_signed(self.key.as_ref())?;
+
+        // Never overwrite progress from a newer build with a format it can't read back
+        if let Some(version) = Self::file_version(&self.save_file_path) {
+            if version > CURRENT_SAVE_VERSION {
+                return Err(MigrationError::NewerVersion(version).into());
             }
         }
 
-        // Write the new save file
-        fs::write(&self.save_file_path, &json)?;
-        
-        // Verify the save was written correctly
-        let read_back = fs::read_to_string(&self.save_file_path)?;
-        if read_back != json {
-            return Err("Save file verification failed".into());
+        self.rotate_backups()?;
+        write_atomic(&self.save_file_path, &json)?;
+        if let Some(key) = &self.key {
+            // Only weakens the downgrade check if it fails, so it doesn't fail the save
+            let _ = key.record_signed();
+        }
+        Ok(())
+    }
+
+    // Copy the current save into a new timestamped backup if the last one is old
+    // enough, then drop the oldest backups beyond the limit
+    fn rotate_backups(&self) -> io::Result<()> {
+        let backups = self.backups();
+        let now = unix_millis();
+        let due = backups
+            .first()
+            .is_none_or(|(taken, _)| now.saturating_sub(*taken) >= self.backup_interval.as_millis());
+
+        if let Ok(existing) = fs::read_to_string(&self.save_file_path) {
+            if SaveData::from_json(&existing).is_err() {
+                // A damaged save is kept aside instead of written over, whenever it's found.
+                // It isn't a backup, so it can't push out good ones
+                write_atomic(&self.damaged_path(now), &existing)?;
+            } else if due {
+                write_atomic(&self.backup_path(now), &existing)?;
+            }
         }
 
-        self.last_save_time = SystemTime::now();
+        for (_, path) in self.backups().into_iter().skip(self.max_backups) {
+            let _ = fs::remove_file(path);
+        }
         Ok(())
     }
 
-    pub fn load_game(&self) -> Result<SaveData, Box<dyn std::error::Error>> {
-        let mut save_data = SaveData::default();
-        let mut loaded = false;
+    // Loads whichever of the save and its backups is the newest one that is
+    // intact, judged by when it was saved. A save that fails its signature still
+    // loads, the caller decides what to make of it. With no save files at all
+    // it's a new game, but files that are all damaged are an error, so they
+    // aren't quietly replaced by a fresh save
+    pub fn load_game_signed(&self) -> Result<(SaveData, Signature), Box<dyn std::error::Error>> {
+        // A save from a newer build is left alone rather than replaced by a backup
+        if let Some(version) = Self::file_version(&self.save_file_path) {
+            if version > CURRENT_SAVE_VERSION {
+                return Err(MigrationError::NewerVersion(version).into());
+            }
+        }
 
-        // Try to load the main save file
-        if Path::new(&self.save_file_path).exists() {
-            if let Ok(json) = fs::read_to_string(&self.save_file_path) {
-                if let Ok(data) = serde_json::from_str(&json) {
-                    save_data = data;
-                    loaded = true;
+        let mut best: Option<(SaveData, Signature)> = None;
+        let mut first_error: Option<String> = None;
+        for path in self.save_candidates() {
+            let json = match fs::read_to_string(&path) {
+                Ok(json) => json,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    first_error.get_or_insert_with(|| describe_failure(&path, &e));
+                    continue;
                 }
+            };
+            let loaded = match SaveData::from_json_signed(&json, self.key.as_ref()) {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    first_error.get_or_insert_with(|| describe_failure(&path, e.as_ref()));
+                    continue;
+                }
+            };
+            // The main save comes first, so it wins ties with its own backup
+            if best.as_ref().is_none_or(|(b, _)| loaded.0.save_timestamp > b.save_timestamp) {
+                best = Some(loaded);
             }
         }
 
-        // If main save failed or has no resources, try the backup
-        if (!loaded || (save_data.player_wood == 0 && save_data.player_copper == 0)) && 
-           Path::new(&self.backup_file_path).exists() {
-            if let Ok(json) = fs::read_to_string(&self.backup_file_path) {
-                if let Ok(backup_data) = serde_json::from_str(&json) {
-                    // Use backup if it has more resources
-                    let backup_data: SaveData = backup_data;
-                    if backup_data.player_wood > save_data.player_wood || 
-                       backup_data.player_copper > save_data.player_copper {
-                        // println!("Loaded backup save with more resources");
-                        save_data = backup_data;
-                    }
+        match (best, first_error) {
+            (Some(best), _) => Ok(best),
+            (None, Some(error)) => Err(format!("no save file could be loaded, {}", error).into()),
+            (None, None) => Ok((SaveData::default(), Signature::Valid)),
+        }
+    }
+
+    // Whether there is anything to load, intact or not
+    pub fn exists(&self) -> bool {
+        self.save_candidates().iter().any(|path| path.exists())
+    }
+
+    // Every save file there is, in the order load_game_signed looks at them, with its
+    // format version and either what it holds or why it can't be loaded
+    pub fn check_files(&self) -> Vec<FileCheck> {
+        self.save_candidates()
+            .into_iter()
+            .filter(|path| path.exists())
+            .map(|path| {
+                let loaded = fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| SaveData::from_json_signed(&json, self.key.as_ref()).map_err(|e| e.to_string()));
+                let version = Self::file_version(&path);
+                (path, version, loaded)
+            })
+            .collect()
+    }
+
+    // The save file, its backups newest first, then the single backup older versions kept
+    fn save_candidates(&self) -> Vec<PathBuf> {
+        let mut candidates = vec![self.save_file_path.clone()];
+        candidates.extend(self.backups().into_iter().map(|(_, path)| path));
+        candidates.push(self.legacy_backup_path());
+        candidates
+    }
+
+    // Timestamped backups next to the save, newest first
+    fn backups(&self) -> Vec<(u128, PathBuf)> {
+        let prefix = format!("{}.backup.", self.file_stem());
+        let entries = match fs::read_dir(parent_dir(&self.save_file_path)) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut backups: Vec<(u128, PathBuf)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let taken = name.strip_prefix(&prefix)?.strip_suffix(".json")?.parse().ok()?;
+                Some((taken, entry.path()))
+            })
+            .collect();
+        backups.sort_by_key(|(taken, _)| Reverse(*taken));
+        backups
+    }
+
+    fn file_stem(&self) -> String {
+        self.save_file_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "keycrafter_save".to_string())
+    }
+
+    fn backup_path(&self, taken: u128) -> PathBuf {
+        parent_dir(&self.save_file_path).join(format!("{}.backup.{}.json", self.file_stem(), taken))
+    }
+
+    fn damaged_path(&self, found: u128) -> PathBuf {
+        parent_dir(&self.save_file_path).join(format!("{}.damaged.{}.json", self.file_stem(), found))
+    }
+
+    fn legacy_backup_path(&self) -> PathBuf {
+        parent_dir(&self.save_file_path).join(format!("{}.backup.json", self.file_stem()))
+    }
+
+    // Format version of the save at a path, if there is a readable one
+    fn file_version(path: &Path) -> Option<u32> {
+        let json = fs::read_to_string(path).ok()?;
+        let value: Value = serde_json::from_str(&json).ok()?;
+        save_migrations::version_of(&value).ok()
+    }
+
+    // Move the save and its backups over from another directory, like the working
+    // directory older versions saved to. Leaves things alone if there is a save here already
+    pub fn adopt_saves_from(&self, old_dir: &Path) -> io::Result<bool> {
+        let file_name = self.save_file_path.file_name().unwrap_or_default();
+        let old = SaveManager::with_path(old_dir.join(file_name));
+        if self.save_file_path.exists() || !old.save_file_path.exists() {
+            return Ok(false);
+        }
+
+        fs::create_dir_all(parent_dir(&self.save_file_path))?;
+        // Backups first, so the save itself arriving last marks the move as done
+        for path in old.save_candidates().into_iter().skip(1) {
+            if let Some(name) = path.file_name() {
+                if path.exists() {
+                    move_file(&path, &parent_dir(&self.save_file_path).join(name))?;
                 }
             }
         }
-
-        Ok(save_data)
+        move_file(&old.save_file_path, &self.save_file_path)?;
+        Ok(true)
     }
 
     pub fn delete_save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        if Path::new(&self.save_file_path).exists() {
-            fs::remove_file(&self.save_file_path)?;
-        }
-        if Path::new(&self.backup_file_path).exists() {
-            fs::remove_file(&self.backup_file_path)?;
+        let mut paths = vec![self.save_file_path.clone(), self.legacy_backup_path()];
+        paths.extend(self.backups().into_iter().map(|(_, path)| path));
+        for path in paths {
+            if path.exists() {
+                fs::remove_file(&path)?;
+            }
         }
         Ok(())
     }
@@ -187,6 +482,10 @@ impl GameStats {
         *self.resources_harvested.entry(resource_type).or_insert(0) += amount;
     }
 
+    pub fn add_resource_generated(&mut self, resource_type: ResourceType, amount: u32) {
+        *self.resources_generated.entry(resource_type).or_insert(0) += amount;
+    }
+
     pub fn add_word_completed(&mut self, word_length: u32, time_taken: f32) {
         self.words_completed += 1;
         self.words_typed += 1;
@@ -237,4 +536,242 @@ impl GameStats {
             format!("{}s", seconds)
         }
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A fresh, empty directory per test under the system temp dir
+    fn temp_save_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("keycrafter-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn save_with(wood: u32, timestamp: u64) -> SaveData {
+        SaveData {
+            player_wood: wood,
+            save_timestamp: timestamp,
+            ..SaveData::default()
+        }
+    }
+
+    // Save manager that backs up on every save, pausing so backups get distinct names
+    fn manager_in(dir: &Path) -> SaveManager {
+        let mut manager = SaveManager::with_path(dir.join("keycrafter_save.json"));
+        manager.backup_interval = Duration::ZERO;
+        manager
+    }
+
+    fn save(manager: &mut SaveManager, data: &SaveData) {
+        manager.save_game(data).unwrap();
+        std::thread::sleep(Duration::from_millis(2));
+    }
+
+    #[test]
+    fn save_round_trips_with_checksum() {
+        let dir = temp_save_dir("round-trip");
+        let mut manager = manager_in(&dir);
+        let mut data = save_with(42, 100);
+        // Floats have to come back bit for bit or the checksum won't match
+        data.stats.average_wpm = 47.3;
+        data.stats.fastest_word_time = Some(0.123);
+        save(&mut manager, &data);
+
+        let json = fs::read_to_string(dir.join("keycrafter_save.json")).unwrap();
+        assert!(json.contains("\"checksum\""));
+        assert_eq!(manager.load_game_signed().unwrap().0.player_wood, 42);
+        assert!(!dir.join("keycrafter_save.json.tmp").exists());
+    }
+
+    #[test]
+    fn world_round_trips() {
+        let fixture: SaveData = serde_json::from_str(include_str!("../tests/fixtures/save_v7.json")).unwrap();
+        let json = fixture.to_json_signed(None).unwrap();
+        // Half-typed words keep their timing, which is a float in the checksum too
+        assert_eq!(SaveData::from_json(&json).unwrap().world, fixture.world);
+    }
+
+    #[test]
+    fn tampered_save_fails_its_checksum() {
+        let json = save_with(42, 100).to_json_signed(None).unwrap();
+        let tampered = json.replace("\"player_wood\": 42", "\"player_wood\": 4200");
+        assert_ne!(json, tampered);
+        assert!(SaveData::from_json(&tampered).is_err());
+    }
+
+    #[test]
+    fn damaged_save_without_backups_is_kept() {
+        let dir = temp_save_dir("damaged");
+        let mut manager = manager_in(&dir);
+        let damaged = save_with(777, 100).to_json_signed(None).unwrap().replace("777", "778");
+        fs::write(dir.join("keycrafter_save.json"), &damaged).unwrap();
+        assert!(manager.exists());
+        assert!(manager.load_game_signed().is_err());
+
+        // Writing over it anyway keeps the damaged file aside first
+        manager.save_game(&save_with(0, 200)).unwrap();
+        let kept: Vec<PathBuf> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.to_string_lossy().contains(".damaged."))
+            .collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(fs::read_to_string(&kept[0]).unwrap(), damaged);
+        assert_eq!(manager.load_game_signed().unwrap().0.player_wood, 0);
+    }
+
+    #[test]
+    fn corrupt_save_falls_back_to_newest_valid_backup() {
+        let dir = temp_save_dir("corrupt");
+        let mut manager = manager_in(&dir);
+        save(&mut manager, &save_with(1, 100));
+        save(&mut manager, &save_with(2, 200));
+        save(&mut manager, &save_with(3, 300));
+
+        // Backups hold the saves from before the last one
+        fs::write(dir.join("keycrafter_save.json"), "{\"player_wood\": 3, trunc").unwrap();
+        assert_eq!(manager.load_game_signed().unwrap().0.player_wood, 2);
+    }
+
+    #[test]
+    fn newest_save_wins_over_richer_backup() {
+        let dir = temp_save_dir("newest");
+        let mut manager = manager_in(&dir);
+        save(&mut manager, &save_with(500, 100));
+        // Spending resources must not bring the old save back
+        save(&mut manager, &save_with(10, 200));
+        assert_eq!(manager.load_game_signed().unwrap().0.player_wood, 10);
+    }
+
+    #[test]
+    fn backups_are_capped() {
+        let dir = temp_save_dir("rotation");
+        let mut manager = manager_in(&dir);
+        for i in 0..(MAX_BACKUPS as u64 + 4) {
+            save(&mut manager, &save_with(i as u32, i));
+        }
+        assert_eq!(manager.backups().len(), MAX_BACKUPS);
+
+        manager.delete_save().unwrap();
+        assert!(manager.backups().is_empty());
+        assert!(!dir.join("keycrafter_save.json").exists());
+    }
+
+    #[test]
+    fn backups_are_spaced_out() {
+        let dir = temp_save_dir("interval");
+        let mut manager = SaveManager::with_path(dir.join("keycrafter_save.json"));
+        for i in 0..4 {
+            save(&mut manager, &save_with(i, i as u64));
+        }
+        // The first save had nothing to back up, the rest fell within the interval
+        assert_eq!(manager.backups().len(), 1);
+    }
+
+    #[test]
+    fn saves_move_from_old_directory() {
+        let old_dir = temp_save_dir("adopt-old");
+        let mut old = manager_in(&old_dir);
+        save(&mut old, &save_with(1, 100));
+        save(&mut old, &save_with(2, 200));
+        fs::write(old_dir.join("keycrafter_save.backup.json"), save_with(0, 50).to_json_signed(None).unwrap()).unwrap();
+
+        let new_dir = temp_save_dir("adopt-new").join("nested");
+        let manager = manager_in(&new_dir);
+        assert!(manager.adopt_saves_from(&old_dir).unwrap());
+        assert_eq!(manager.load_game_signed().unwrap().0.player_wood, 2);
+        assert_eq!(manager.backups().len(), 1);
+        assert!(new_dir.join("keycrafter_save.backup.json").exists());
+        assert!(!old_dir.join("keycrafter_save.json").exists());
+
+        // Nothing to do once the save has moved
+        assert!(!manager.adopt_saves_from(&old_dir).unwrap());
+    }
+
+    fn key_in(dir: &Path) -> InstallKey {
+        InstallKey::load_or_create(&dir.join("install.key")).unwrap()
+    }
+
+    #[test]
+    fn signed_saves_verify() {
+        let dir = temp_save_dir("signed");
+        let mut manager = manager_in(&dir).with_key(key_in(&dir));
+        save(&mut manager, &save_with(42, 100));
+        let (data, signature) = manager.load_game_signed().unwrap();
+        assert_eq!(data.player_wood, 42);
+        assert_eq!(signature, Signature::Valid);
+    }
+
+    #[test]
+    fn edited_saves_fail_their_signature() {
+        let dir = temp_save_dir("edited");
+        let mut manager = manager_in(&dir).with_key(key_in(&dir));
+        save(&mut manager, &save_with(42, 100));
+
+        // Edit the save and fix up the checksum, as someone editing it by hand would
+        let json = fs::read_to_string(dir.join("keycrafter_save.json")).unwrap();
+        let mut value = parse_checked(&json).unwrap();
+        value["player_wood"] = Value::from(9999);
+        let checksum = checksum_of(&value);
+        value["checksum"] = Value::String(checksum);
+        fs::write(dir.join("keycrafter_save.json"), value.to_string()).unwrap();
+
+        let (data, signature) = manager.load_game_signed().unwrap();
+        assert_eq!(data.player_wood, 9999);
+        assert_eq!(signature, Signature::Invalid);
+
+        // Stripping the signature doesn't help either
+        let fields = value.as_object_mut().unwrap();
+        fields.remove("checksum");
+        fields.remove("signature");
+        let checksum = checksum_of(&value);
+        value["checksum"] = Value::String(checksum);
+        fs::write(dir.join("keycrafter_save.json"), value.to_string()).unwrap();
+        assert_eq!(manager.load_game_signed().unwrap().1, Signature::Invalid);
+    }
+
+    #[test]
+    fn unsigned_saves_are_invalid_once_this_install_has_signed() {
+        let dir = temp_save_dir("downgraded");
+        let mut manager = manager_in(&dir).with_key(key_in(&dir));
+        save(&mut manager, &save_with(42, 100));
+
+        // Passing an edit off as a save from before signing
+        let json = fs::read_to_string(dir.join("keycrafter_save.json")).unwrap();
+        let mut value = parse_checked(&json).unwrap();
+        value["player_wood"] = Value::from(99999);
+        let fields = value.as_object_mut().unwrap();
+        for field in ["version", "signature", "checksum"] {
+            fields.remove(field);
+        }
+        fs::write(dir.join("keycrafter_save.json"), value.to_string()).unwrap();
+
+        let (data, signature) = manager.load_game_signed().unwrap();
+        assert_eq!(data.player_wood, 99999);
+        assert_eq!(signature, Signature::Invalid);
+    }
+
+    #[test]
+    fn saves_from_another_install_fail_their_signature() {
+        let dir = temp_save_dir("other-install");
+        let mut manager = manager_in(&dir).with_key(key_in(&dir));
+        save(&mut manager, &save_with(42, 100));
+
+        fs::remove_file(dir.join("install.key")).unwrap();
+        let other = manager_in(&dir).with_key(key_in(&dir));
+        assert_eq!(other.load_game_signed().unwrap().1, Signature::Invalid);
+    }
+
+    #[test]
+    fn saves_without_checksum_still_load() {
+        let dir = temp_save_dir("legacy");
+        fs::write(dir.join("keycrafter_save.json"), include_str!("../tests/fixtures/save_v1.json")).unwrap();
+        let manager = manager_in(&dir).with_key(key_in(&dir));
+        let (data, signature) = manager.load_game_signed().unwrap();
+        assert_eq!(data.player_wood, 21);
+        assert_eq!(signature, Signature::Unchecked);
+    }
+}
@@ -0,0 +1,239 @@
+// Seeded terrain generation: fractal value noise shaped by a radial falloff
+// so every island gets its own irregular coastline, bays, lakes and islets.
+// A second noise layer decides moisture, which splits land into biomes.
+// Finally two winding roads are laid across the island through its centre.
+
+use crate::tile_map::{TileKind, TileMap};
+
+// Elevation thresholds
+const LAND_LEVEL: f32 = 0.5;
+const SHALLOW_LEVEL: f32 = 0.38;
+const LAKE_LEVEL: f32 = 0.32;  // Noise below this carves lakes out of inland areas
+const ISLET_LEVEL: f32 = 0.7;  // Noise above this raises islets out of the sea
+const BEACH_BAND: f32 = 0.06;  // Land this close to sea level becomes sand
+const ROCK_LEVEL: f32 = 0.82;  // Noise above this on the mainland forms rocky outcrops
+const FOREST_MOISTURE: f32 = 0.55;  // Moisture above this grows forest floor
+const ROAD_WANDER: f32 = 8.0;  // How far roads drift from the centre lines, in tiles
+
+pub struct TerrainGenerator {
+    seed: u64,
+    width: i32,
+    height: i32,
+    octaves: u32,
+    scale: f32,  // Size of the base noise features in tiles
+}
+
+impl TerrainGenerator {
+    pub fn new(seed: u64, width: i32, height: i32) -> Self {
+        Self {
+            seed,
+            width,
+            height,
+            octaves: 4,
+            scale: 12.0,
+        }
+    }
+
+    pub fn generate(&self) -> TileMap {
+        let mut tiles = Vec::with_capacity((self.width * self.height) as usize);
+        let center_x = self.width as f32 / 2.0;
+        let center_y = self.height as f32 / 2.0;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                // Terminal cells are roughly twice as tall as wide, so the noise
+                // is sampled with y stretched to keep features looking round
+                let nx = x as f32 / self.scale;
+                let ny = y as f32 * 2.0 / self.scale;
+                let noise = self.fractal_noise(nx, ny, 0);
+                let moisture = self.fractal_noise(nx * 0.7, ny * 0.7, 8);
+
+                // Elliptical distance from the centre, 0.0 at centre and 1.0 at the map edge
+                let dx = (x as f32 - center_x) / center_x;
+                let dy = (y as f32 - center_y) / center_y;
+                let distance = (dx * dx + dy * dy).sqrt();
+
+                // Falloff keeps the middle as land and pushes the edges into the sea,
+                // while the noise bends the coastline into bays and headlands
+                let falloff = 1.0 - distance.powf(2.2);
+                let elevation = falloff * 0.75 + noise * 0.55 - 0.1;
+
+                let on_edge = x == 0 || y == 0 || x == self.width - 1 || y == self.height - 1;
+                let tile = if on_edge {
+                    // A rim of open sea, so no islet or shallows run off the map
+                    TileKind::DeepWater
+                } else if elevation >= LAND_LEVEL {
+                    let near_spawn = distance <= 0.25;
+                    if noise < LAKE_LEVEL && !near_spawn && distance < 0.7 {
+                        // Carve lakes from low-noise pockets, but never at the spawn point
+                        TileKind::ShallowWater
+                    } else if elevation < LAND_LEVEL + BEACH_BAND {
+                        TileKind::Sand
+                    } else if noise > ROCK_LEVEL && !near_spawn {
+                        TileKind::Rock
+                    } else if moisture > FOREST_MOISTURE {
+                        TileKind::ForestFloor
+                    } else {
+                        TileKind::Grass
+                    }
+                } else if noise > ISLET_LEVEL && distance > 0.8 {
+                    // Strong noise peaks out at sea poke up as small sandy islets
+                    TileKind::Sand
+                } else if elevation >= SHALLOW_LEVEL {
+                    TileKind::ShallowWater
+                } else {
+                    TileKind::DeepWater
+                };
+                tiles.push(tile);
+            }
+        }
+
+        self.lay_roads(&mut tiles);
+        TileMap::new(self.width, self.height, tiles)
+    }
+
+    // One east-west and one north-south road through the centre. Each drifts
+    // with low-frequency noise and only paves land, so water and rock break it up.
+    fn lay_roads(&self, tiles: &mut [TileKind]) {
+        let center_x = self.width / 2;
+        let center_y = self.height / 2;
+        let wander = |t: i32, layer: u32| -> i32 {
+            let noise = self.fractal_noise(t as f32 / (self.scale * 2.0), 0.5, layer);
+            ((noise - 0.5) * 2.0 * ROAD_WANDER) as i32
+        };
+
+        // Anchor both roads so they cross at the centre, where the player lands
+        let offset_y = wander(center_x, 16);
+        let offset_x = wander(center_y, 20);
+
+        let mut previous_y = None;
+        for x in 0..self.width {
+            let y = center_y + (wander(x, 16) - offset_y) / 2;
+            // Fill vertical gaps so the road stays connected when it drifts
+            let from = previous_y.unwrap_or(y);
+            for road_y in from.min(y)..=from.max(y) {
+                self.pave(tiles, x, road_y);
+            }
+            previous_y = Some(y);
+        }
+
+        let mut previous_x = None;
+        for y in 0..self.height {
+            let x = center_x + wander(y, 20) - offset_x;
+            let from = previous_x.unwrap_or(x);
+            for road_x in from.min(x)..=from.max(x) {
+                self.pave(tiles, road_x, y);
+            }
+            previous_x = Some(x);
+        }
+    }
+
+    fn pave(&self, tiles: &mut [TileKind], x: i32, y: i32) {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return;
+        }
+        let tile = &mut tiles[(y * self.width + x) as usize];
+        if matches!(tile, TileKind::Grass | TileKind::ForestFloor | TileKind::Sand) {
+            *tile = TileKind::Road;
+        }
+    }
+
+    // Sum of several noise octaves, normalised to 0.0..1.0
+    fn fractal_noise(&self, x: f32, y: f32, layer: u32) -> f32 {
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        let mut max_value = 0.0;
+
+        for octave in 0..self.octaves {
+            total += self.value_noise(x * frequency, y * frequency, layer + octave) * amplitude;
+            max_value += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+
+        total / max_value
+    }
+
+    // Smoothly interpolated random values on an integer lattice
+    fn value_noise(&self, x: f32, y: f32, octave: u32) -> f32 {
+        let x0 = x.floor() as i32;
+        let y0 = y.floor() as i32;
+        let tx = smoothstep(x - x0 as f32);
+        let ty = smoothstep(y - y0 as f32);
+
+        let v00 = self.lattice(x0, y0, octave);
+        let v10 = self.lattice(x0 + 1, y0, octave);
+        let v01 = self.lattice(x0, y0 + 1, octave);
+        let v11 = self.lattice(x0 + 1, y0 + 1, octave);
+
+        let top = v00 + (v10 - v00) * tx;
+        let bottom = v01 + (v11 - v01) * tx;
+        top + (bottom - top) * ty
+    }
+
+    // Deterministic pseudo-random value in 0.0..1.0 for a lattice point
+    fn lattice(&self, x: i32, y: i32, octave: u32) -> f32 {
+        let mut h = self.seed
+            ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+            ^ (octave as u64).wrapping_mul(0x1656_67B1_9E37_79F9);
+        // SplitMix64 finaliser
+        h = (h ^ (h >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        h = (h ^ (h >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        h ^= h >> 31;
+        (h >> 11) as f32 / (1u64 << 53) as f32
+    }
+}
+
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiles(map: &TileMap) -> Vec<TileKind> {
+        (0..map.height()).flat_map(|y| (0..map.width()).map(move |x| map.get(x, y))).collect()
+    }
+
+    fn edge(map: &TileMap) -> Vec<(i32, i32)> {
+        let (w, h) = (map.width(), map.height());
+        (0..w).flat_map(|x| [(x, 0), (x, h - 1)])
+            .chain((0..h).flat_map(|y| [(0, y), (w - 1, y)]))
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_same_island() {
+        let map = TerrainGenerator::new(42, 96, 30).generate();
+        assert_eq!(tiles(&map), tiles(&TerrainGenerator::new(42, 96, 30).generate()));
+        assert_ne!(tiles(&map), tiles(&TerrainGenerator::new(43, 96, 30).generate()));
+    }
+
+    #[test]
+    fn edges_are_deep_water() {
+        for seed in 0..200 {
+            let map = TerrainGenerator::new(seed, 96, 30).generate();
+            for (x, y) in edge(&map) {
+                assert_eq!(map.get(x, y), TileKind::DeepWater, "seed {} at {},{}", seed, x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn islands_have_land_in_the_middle_and_every_biome() {
+        // Seeds and sizes from islands.rs
+        for (seed, width, height) in [(0x5EED_0001, 96, 30), (0xC0FF_EE42, 120, 36), (0x0B5E_55ED, 140, 40), (0xE3BE_7155, 160, 44)] {
+            let map = TerrainGenerator::new(seed, width, height).generate();
+            let centre = map.get(width / 2, height / 2);
+            assert!(centre.is_land() && centre.is_walkable(), "{:x} lands on {:?}", seed, centre);
+
+            let tiles = tiles(&map);
+            for kind in [TileKind::Grass, TileKind::ForestFloor, TileKind::Sand, TileKind::Road, TileKind::ShallowWater, TileKind::DeepWater] {
+                assert!(tiles.contains(&kind), "{:x} has no {:?}", seed, kind);
+            }
+        }
+    }
+}
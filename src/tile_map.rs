@@ -0,0 +1,187 @@
+use crate::resource_types::ResourceType;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TileKind {
+    Grass,
+    Sand,
+    ShallowWater,
+    DeepWater,
+    Rock,
+    ForestFloor,
+    Road,
+}
+
+impl TileKind {
+    pub fn is_walkable(&self) -> bool {
+        self.movement_cost().is_some()
+    }
+
+    // Relative cost of stepping onto this tile; None if it can't be entered.
+    // Grass is the baseline, roads are twice as fast
+    pub fn movement_cost(&self) -> Option<u32> {
+        match self {
+            TileKind::Road => Some(1),
+            TileKind::Grass => Some(2),
+            TileKind::ForestFloor => Some(3),
+            TileKind::Sand => Some(3),
+            TileKind::ShallowWater => Some(6),
+            TileKind::DeepWater | TileKind::Rock => None,
+        }
+    }
+
+    pub fn is_land(&self) -> bool {
+        !matches!(self, TileKind::ShallowWater | TileKind::DeepWater)
+    }
+
+    // Which resource nodes are allowed to grow on this tile
+    pub fn allows_resource(&self, resource_type: &ResourceType) -> bool {
+        match resource_type {
+            ResourceType::Wood => matches!(self, TileKind::Grass | TileKind::ForestFloor),
+            ResourceType::Copper => matches!(self, TileKind::Grass | TileKind::Sand),
+        }
+    }
+}
+
+pub struct TileMap {
+    width: i32,
+    height: i32,
+    tiles: Vec<TileKind>,
+}
+
+impl TileMap {
+    pub fn new(width: i32, height: i32, tiles: Vec<TileKind>) -> Self {
+        debug_assert_eq!(tiles.len(), (width * height) as usize);
+        Self { width, height, tiles }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> TileKind {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return TileKind::DeepWater;
+        }
+        self.tiles[(y * self.width + x) as usize]
+    }
+
+    pub fn movement_cost(&self, x: i32, y: i32) -> Option<u32> {
+        self.get(x, y).movement_cost()
+    }
+
+    // True if a resource can be placed with its footprint at (x, y). Every tile under
+    // the object must allow this resource, and the margin around it must be land
+    // the player can walk on, so rock doesn't wall the node in.
+    pub fn can_place_resource(&self, resource_type: &ResourceType, x: i32, y: i32, w: i32, h: i32, margin: i32) -> bool {
+        for ty in (y - margin)..(y + h + margin) {
+            for tx in (x - margin)..(x + w + margin) {
+                let tile = self.get(tx, ty);
+                let under_object = tx >= x && tx < x + w && ty >= y && ty < y + h;
+                let fits = if under_object {
+                    tile.allows_resource(resource_type)
+                } else {
+                    tile.is_land() && tile.is_walkable()
+                };
+                if !fits {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    // Closest walkable tile to a point, searching outward in rings
+    pub fn nearest_walkable(&self, x: i32, y: i32) -> Option<(i32, i32)> {
+        let tile = self.get(x, y);
+        if tile.is_land() && tile.is_walkable() {
+            return Some((x, y));
+        }
+        let max_radius = self.width.max(self.height);
+        for radius in 1..max_radius {
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    if dx.abs() != radius && dy.abs() != radius {
+                        continue;  // Only the ring itself
+                    }
+                    let tile = self.get(x + dx, y + dy);
+                    if tile.is_land() && tile.is_walkable() {
+                        return Some((x + dx, y + dy));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Map from rows of tiles: '.' grass, 's' sand, 'f' forest floor, ':' road,
+    // '-' shallow water, '~' deep water, '^' rock
+    fn map_of(rows: &[&str]) -> TileMap {
+        let tiles = rows.iter()
+            .flat_map(|row| row.chars())
+            .map(|c| match c {
+                's' => TileKind::Sand,
+                'f' => TileKind::ForestFloor,
+                ':' => TileKind::Road,
+                '-' => TileKind::ShallowWater,
+                '~' => TileKind::DeepWater,
+                '^' => TileKind::Rock,
+                _ => TileKind::Grass,
+            })
+            .collect();
+        TileMap::new(rows[0].len() as i32, rows.len() as i32, tiles)
+    }
+
+    #[test]
+    fn resources_need_their_ground_and_a_walkable_margin() {
+        let map = map_of(&[
+            "~~~~~~~~",
+            "~..ff.^~",
+            "~..ff..~",
+            "~.sss:.~",
+            "~~~~~~~~",
+        ]);
+        // Trees on forest with grass and road around them
+        assert!(map.can_place_resource(&ResourceType::Wood, 3, 1, 2, 2, 0));
+        assert!(!map.can_place_resource(&ResourceType::Wood, 3, 1, 2, 2, 1));
+        assert!(map.can_place_resource(&ResourceType::Wood, 3, 2, 1, 1, 1));
+        // Copper grows on sand but not forest, and rock in the margin walls it in
+        assert!(map.can_place_resource(&ResourceType::Copper, 3, 3, 1, 1, 0));
+        assert!(!map.can_place_resource(&ResourceType::Wood, 3, 3, 1, 1, 0));
+        assert!(map.can_place_resource(&ResourceType::Copper, 2, 2, 1, 1, 1));
+        assert!(!map.can_place_resource(&ResourceType::Copper, 5, 2, 1, 1, 1));
+        // Off the map is deep water, so nothing fits against the edge
+        assert!(!map.can_place_resource(&ResourceType::Wood, 0, 0, 1, 1, 0));
+    }
+
+    #[test]
+    fn nearest_walkable_is_never_blocked() {
+        let map = map_of(&[
+            "~~~~~~~~~",
+            "~^^^^---~",
+            "~^~~^-.-~",
+            "~^^^^---~",
+            "~~~~~~~~:",
+        ]);
+        for y in -2..map.height() + 2 {
+            for x in -2..map.width() + 2 {
+                let (nx, ny) = map.nearest_walkable(x, y).unwrap();
+                let tile = map.get(nx, ny);
+                assert!(tile.is_land() && tile.is_walkable(), "{},{} gave {:?}", x, y, tile);
+            }
+        }
+        assert_eq!(map.nearest_walkable(2, 2), Some((6, 2)));
+        assert_eq!(map.nearest_walkable(8, 4), Some((8, 4)));
+
+        let sea = map_of(&["~~~", "~^~", "---"]);
+        assert_eq!(sea.nearest_walkable(1, 1), None);
+    }
+}
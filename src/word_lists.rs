@@ -35,4 +35,13 @@ pub enum WordDifficulty {
     Easy,   // 3-4 letters
     Medium, // 5-6 letters
     Hard,   // 7+ letters
+}
+
+impl WordDifficulty {
+    pub fn harder(self) -> Self {
+        match self {
+            WordDifficulty::Easy => WordDifficulty::Medium,
+            WordDifficulty::Medium | WordDifficulty::Hard => WordDifficulty::Hard,
+        }
+    }
 } 
\ No newline at end of file
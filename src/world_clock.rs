@@ -0,0 +1,335 @@
+// Game-time clock with a day/night cycle and changing weather. It runs only
+// while the game does, so a long session passes through every phase. Other
+// systems ask it how things should look and play right now.
+
+use std::time::{Duration, Instant};
+use rand::Rng;
+use crate::resource_types::ResourceType;
+
+// One full day, dawn to dawn
+const DAY_LENGTH_SECS: f32 = 16.0 * 60.0;
+// Fraction of the day each phase starts at
+const DAY_STARTS: f32 = 0.08;
+const DUSK_STARTS: f32 = 0.55;
+const NIGHT_STARTS: f32 = 0.65;
+
+// How long a spell of weather lasts, in seconds
+const WEATHER_MIN_SECS: u64 = 90;
+const WEATHER_MAX_SECS: u64 = 240;
+
+const LIGHTNING_FLASH: Duration = Duration::from_millis(150);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayPhase {
+    Dawn,
+    Day,
+    Dusk,
+    Night,
+}
+
+impl DayPhase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DayPhase::Dawn => "Dawn",
+            DayPhase::Day => "Day",
+            DayPhase::Dusk => "Dusk",
+            DayPhase::Night => "Night",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Weather {
+    Clear,
+    Rain,
+    Fog,
+    Storm,
+}
+
+impl Weather {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Weather::Clear => "Clear",
+            Weather::Rain => "Rain",
+            Weather::Fog => "Fog",
+            Weather::Storm => "Storm",
+        }
+    }
+
+    // Relative chance of each weather when the current spell ends
+    fn weight(&self) -> u32 {
+        match self {
+            Weather::Clear => 5,
+            Weather::Rain => 2,
+            Weather::Fog => 2,
+            Weather::Storm => 1,
+        }
+    }
+
+    fn announcement(&self) -> &'static str {
+        match self {
+            Weather::Clear => "The skies clear",
+            Weather::Rain => "It starts to rain",
+            Weather::Fog => "Fog rolls in",
+            Weather::Storm => "A storm breaks!",
+        }
+    }
+}
+
+pub struct WorldClock {
+    elapsed: f32,  // Seconds of game time since the clock started
+    last_tick: Instant,
+    phase: DayPhase,
+    weather: Weather,
+    weather_left: Duration,
+    flash_until: Option<Instant>,  // Lightning is lighting up the island until then
+}
+
+impl WorldClock {
+    pub fn new() -> Self {
+        Self {
+            elapsed: DAY_STARTS * DAY_LENGTH_SECS,  // Start the first session in the morning
+            last_tick: Instant::now(),
+            phase: DayPhase::Day,
+            weather: Weather::Clear,
+            weather_left: Duration::from_secs(WEATHER_MAX_SECS),
+            flash_until: None,
+        }
+    }
+
+    // Advance the clock. Returns a message when the phase or weather changes
+    pub fn update(&mut self) -> Option<&'static str> {
+        self.advance(Instant::now(), &mut rand::thread_rng())
+    }
+
+    fn advance(&mut self, now: Instant, rng: &mut impl Rng) -> Option<&'static str> {
+        let dt = now.duration_since(self.last_tick);
+        self.last_tick = now;
+        self.elapsed += dt.as_secs_f32();
+
+        if self.weather == Weather::Storm && self.flash_until.is_none() && rng.gen_ratio(1, 150) {
+            self.flash_until = Some(now + LIGHTNING_FLASH);
+        }
+        if self.flash_until.is_some_and(|until| now >= until) {
+            self.flash_until = None;
+        }
+
+        self.weather_left = self.weather_left.saturating_sub(dt);
+        if self.weather_left.is_zero() {
+            let previous = self.weather;
+            self.weather = Self::roll_weather(rng);
+            self.weather_left = Duration::from_secs(rng.gen_range(WEATHER_MIN_SECS..=WEATHER_MAX_SECS));
+            if self.weather != previous {
+                return Some(self.weather.announcement());
+            }
+        }
+
+        let phase = Self::phase_at(self.elapsed);
+        if phase != self.phase {
+            self.phase = phase;
+            return match phase {
+                DayPhase::Dawn => Some("The sun rises"),
+                DayPhase::Night => Some("Night falls"),
+                _ => None,
+            };
+        }
+        None
+    }
+
+    fn roll_weather(rng: &mut impl Rng) -> Weather {
+        let all = [Weather::Clear, Weather::Rain, Weather::Fog, Weather::Storm];
+        let total: u32 = all.iter().map(|w| w.weight()).sum();
+        let mut value = rng.gen_range(0..total);
+        for weather in all {
+            if value < weather.weight() {
+                return weather;
+            }
+            value -= weather.weight();
+        }
+        Weather::Clear
+    }
+
+    fn phase_at(elapsed: f32) -> DayPhase {
+        let time_of_day = (elapsed / DAY_LENGTH_SECS).fract();
+        if time_of_day < DAY_STARTS {
+            DayPhase::Dawn
+        } else if time_of_day < DUSK_STARTS {
+            DayPhase::Day
+        } else if time_of_day < NIGHT_STARTS {
+            DayPhase::Dusk
+        } else {
+            DayPhase::Night
+        }
+    }
+
+    pub fn phase(&self) -> DayPhase {
+        self.phase
+    }
+
+    pub fn weather(&self) -> Weather {
+        self.weather
+    }
+
+    pub fn lightning(&self) -> bool {
+        self.flash_until.is_some()
+    }
+
+    // "Night, Rain" for the title bar
+    pub fn label(&self) -> String {
+        match self.weather {
+            Weather::Clear => self.phase.label().to_string(),
+            weather => format!("{}, {}", self.phase.label(), weather.label()),
+        }
+    }
+
+    // Rain makes trees spring up, storms and night turn up more ore
+    pub fn spawn_multiplier(&self, resource_type: &ResourceType) -> f32 {
+        let weather = match (self.weather, resource_type) {
+            (Weather::Rain, ResourceType::Wood) => 1.5,
+            (Weather::Storm, ResourceType::Copper) => 2.0,
+            _ => 1.0,
+        };
+        let time = match (self.phase, resource_type) {
+            (DayPhase::Night, ResourceType::Copper) => 1.5,
+            _ => 1.0,
+        };
+        weather * time
+    }
+
+    // Fog hides the preview of each node's next word
+    pub fn hides_next_word(&self) -> bool {
+        self.weather == Weather::Fog
+    }
+
+    // Storms deal out harder words
+    pub fn harder_words(&self) -> bool {
+        self.weather == Weather::Storm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // A clock whose weather stays put until a test says otherwise
+    fn clock() -> WorldClock {
+        let mut clock = WorldClock::new();
+        clock.weather_left = Duration::from_secs(u32::MAX as u64);
+        clock
+    }
+
+    fn at(clock: &WorldClock, day_fraction: f32) -> Instant {
+        let secs = day_fraction * DAY_LENGTH_SECS - clock.elapsed;
+        clock.last_tick + Duration::from_secs_f32(secs)
+    }
+
+    #[test]
+    fn phases_follow_the_time_of_day() {
+        let mut rng = StdRng::seed_from_u64(40);
+        let mut clock = clock();
+        assert_eq!(clock.phase(), DayPhase::Day);
+
+        let steps = [
+            (0.5, DayPhase::Day, None),
+            (0.56, DayPhase::Dusk, None),
+            (0.66, DayPhase::Night, Some("Night falls")),
+            (0.99, DayPhase::Night, None),
+            (1.01, DayPhase::Dawn, Some("The sun rises")),
+            (1.09, DayPhase::Day, None),
+            (1.7, DayPhase::Night, Some("Night falls")),
+        ];
+        for (day_fraction, phase, message) in steps {
+            let now = at(&clock, day_fraction);
+            assert_eq!(clock.advance(now, &mut rng), message, "at {}", day_fraction);
+            assert_eq!(clock.phase(), phase, "at {}", day_fraction);
+        }
+    }
+
+    #[test]
+    fn weather_changes_when_its_spell_ends() {
+        let mut rng = StdRng::seed_from_u64(41);
+        let mut clock = WorldClock::new();
+        assert_eq!(clock.weather(), Weather::Clear);
+
+        // Half a spell in, nothing has changed yet
+        clock.elapsed = 0.3 * DAY_LENGTH_SECS;
+        let mut now = clock.last_tick + Duration::from_secs(WEATHER_MAX_SECS / 2);
+        assert_eq!(clock.advance(now, &mut rng), None);
+        assert_eq!(clock.weather(), Weather::Clear);
+
+        let mut seen = Vec::new();
+        for _ in 0..60 {
+            let previous = clock.weather();
+            // Keep the time of day still, so only the weather can speak up
+            clock.elapsed = 0.3 * DAY_LENGTH_SECS;
+            now += clock.weather_left;
+            let message = clock.advance(now, &mut rng);
+            if clock.weather() == previous {
+                assert_eq!(message, None);
+            } else {
+                assert_eq!(message, Some(clock.weather().announcement()));
+            }
+            let spell = clock.weather_left.as_secs();
+            assert!((WEATHER_MIN_SECS..=WEATHER_MAX_SECS).contains(&spell), "{}s of {:?}", spell, clock.weather());
+            seen.push(clock.weather());
+        }
+        for weather in [Weather::Clear, Weather::Rain, Weather::Fog, Weather::Storm] {
+            assert!(seen.contains(&weather), "never got {:?}", weather);
+        }
+    }
+
+    #[test]
+    fn storms_flash_briefly() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut clock = clock();
+        clock.weather = Weather::Storm;
+        let mut now = clock.last_tick;
+        let mut flashes = 0;
+        for _ in 0..3000 {
+            now += Duration::from_millis(50);
+            clock.advance(now, &mut rng);
+            if clock.lightning() {
+                flashes += 1;
+                // Gone once the flash is over
+                now += LIGHTNING_FLASH;
+                clock.advance(now, &mut rng);
+                assert!(!clock.lightning());
+            }
+        }
+        assert!(flashes > 0);
+
+        clock.weather = Weather::Clear;
+        for _ in 0..3000 {
+            now += Duration::from_millis(50);
+            clock.advance(now, &mut rng);
+            assert!(!clock.lightning());
+        }
+    }
+
+    #[test]
+    fn weather_and_time_change_how_the_island_plays() {
+        let mut clock = clock();
+        assert_eq!(clock.spawn_multiplier(&ResourceType::Wood), 1.0);
+        assert_eq!(clock.spawn_multiplier(&ResourceType::Copper), 1.0);
+        assert!(!clock.hides_next_word() && !clock.harder_words());
+        assert_eq!(clock.label(), "Day");
+
+        clock.weather = Weather::Rain;
+        assert_eq!(clock.spawn_multiplier(&ResourceType::Wood), 1.5);
+        assert_eq!(clock.spawn_multiplier(&ResourceType::Copper), 1.0);
+
+        clock.weather = Weather::Fog;
+        assert!(clock.hides_next_word() && !clock.harder_words());
+
+        // Night and a storm together stack for ore
+        let night = at(&clock, 0.7);
+        clock.advance(night, &mut StdRng::seed_from_u64(43));
+        clock.weather = Weather::Storm;
+        assert_eq!(clock.spawn_multiplier(&ResourceType::Copper), 3.0);
+        assert_eq!(clock.spawn_multiplier(&ResourceType::Wood), 1.0);
+        assert!(clock.harder_words() && !clock.hides_next_word());
+        assert_eq!(clock.label(), "Night, Storm");
+    }
+}
@@ -0,0 +1,56 @@
+// The world as it stood when the game was saved: where the player is, every
+// island's resource nodes with their words and remaining harvests, and any
+// half-typed words, recipes and voyages. Loading it puts the player back
+// exactly where they left off instead of on a freshly spawned island.
+// Islands and recipes are stored by name so reordering them doesn't scramble a save.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::movement::QueuedHarvest;
+use crate::pathfinding::Position;
+use crate::resource_types::ResourceType;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WorldState {
+    pub current_island: String,
+    pub player: PlayerState,
+    pub islands: Vec<IslandNodes>,  // Every island that has nodes, the current one included
+    pub recipe_inputs: HashMap<String, String>,  // Recipe name to what has been typed of it
+    pub voyage_inputs: HashMap<String, String>,  // Destination name to what has been typed of it
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PlayerState {
+    pub position: Position,
+    pub path: Vec<Position>,
+    pub target: Option<Position>,
+    pub move_points: u32,
+    pub walking_to: Option<QueuedHarvest>,  // Walking mode: the harvest being walked to
+    pub walk_queue: Vec<QueuedHarvest>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IslandNodes {
+    pub island: String,
+    pub nodes: Vec<NodeState>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NodeState {
+    pub position: Position,
+    pub resource_type: ResourceType,
+    pub craft_sentence: String,
+    pub next_craft_sentence: String,
+    pub current_input: String,
+    pub harvests_remaining: u32,
+    pub max_harvests: u32,
+    pub path: Vec<Position>,
+    pub word_seconds: Option<f32>,  // Time spent on the current word so far
+    pub mistakes: u32,
+}
+
+impl WorldState {
+    pub fn island(&self, name: &str) -> Option<&IslandNodes> {
+        self.islands.iter().find(|island| island.island == name)
+    }
+}